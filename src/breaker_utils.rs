@@ -0,0 +1,263 @@
+use std::{
+    fmt,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use crate::logger::{LogLevel, log_sync};
+
+/// State of a [`CircuitBreaker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    /// Calls go through; failures are counted.
+    Closed,
+    /// Calls fail fast until the reset timeout elapses.
+    Open,
+    /// One trial call is allowed to decide whether to close again.
+    HalfOpen,
+}
+
+/// Error returned by [`CircuitBreaker::call`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BreakerError<E> {
+    /// The breaker is open; the call wasn't attempted.
+    Open,
+    /// The call ran and failed.
+    Failed(E),
+}
+
+impl<E: fmt::Display> fmt::Display for BreakerError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Open => f.write_str("circuit breaker is open"),
+            Self::Failed(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for BreakerError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Open => None,
+            Self::Failed(err) => Some(err),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Inner {
+    state: BreakerState,
+    failures: u32,
+    opened_at: Instant,
+    trial_running: bool,
+}
+
+/// Stops calling a failing dependency for a while, then probes it again.
+///
+/// Opens after `failure_threshold` consecutive failures, fails fast while open,
+/// and lets one trial call through once `reset_timeout` has passed. State
+/// transitions are recorded through `Log`.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    reset_timeout: Duration,
+    inner: Mutex<Inner>,
+}
+
+impl CircuitBreaker {
+    /// Creates a closed breaker.
+    pub fn new(failure_threshold: u32, reset_timeout: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            reset_timeout,
+            inner: Mutex::new(Inner {
+                state: BreakerState::Closed,
+                failures: 0,
+                opened_at: Instant::now(),
+                trial_running: false,
+            }),
+        }
+    }
+
+    /// Returns the current state.
+    pub fn state(&self) -> BreakerState {
+        let mut inner = self.inner.lock().unwrap();
+        let change = self.refresh(&mut inner);
+        let state = inner.state;
+        drop(inner);
+        log_transition(change);
+        state
+    }
+
+    /// Runs `f` unless the breaker is open, updating the breaker with the outcome.
+    ///
+    /// A trial call that panics counts as a failure and reopens the breaker.
+    pub fn call<T, E, F>(&self, f: F) -> Result<T, BreakerError<E>>
+    where
+        F: FnOnce() -> Result<T, E>,
+    {
+        let mut inner = self.inner.lock().unwrap();
+        let change = self.refresh(&mut inner);
+        // `None` if the call is rejected, otherwise whether it is the half-open trial.
+        let admitted = match inner.state {
+            BreakerState::Open => None,
+            BreakerState::HalfOpen if inner.trial_running => None,
+            BreakerState::HalfOpen => {
+                inner.trial_running = true;
+                Some(true)
+            }
+            BreakerState::Closed => Some(false),
+        };
+        drop(inner);
+        log_transition(change);
+        let Some(is_trial) = admitted else {
+            return Err(BreakerError::Open);
+        };
+
+        let guard = is_trial.then_some(TrialGuard { breaker: self });
+        let result = f();
+        std::mem::forget(guard);
+
+        let mut inner = self.inner.lock().unwrap();
+        if is_trial {
+            inner.trial_running = false;
+        }
+        let change = match &result {
+            Ok(_) => {
+                inner.failures = 0;
+                is_trial
+                    .then(|| transition(&mut inner, BreakerState::Closed, "trial call succeeded"))
+            }
+            Err(_) => {
+                inner.failures = inner.failures.saturating_add(1);
+                if is_trial {
+                    Some(transition(
+                        &mut inner,
+                        BreakerState::Open,
+                        "trial call failed",
+                    ))
+                } else if inner.state == BreakerState::Closed
+                    && inner.failures >= self.failure_threshold
+                {
+                    let reason = format!("{} consecutive failures", inner.failures);
+                    Some(transition(&mut inner, BreakerState::Open, &reason))
+                } else {
+                    None
+                }
+            }
+        };
+        drop(inner);
+        log_transition(change);
+        result.map_err(BreakerError::Failed)
+    }
+
+    /// Forces the breaker back to closed and clears the failure count.
+    pub fn reset(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.failures = 0;
+        inner.trial_running = false;
+        let change = (inner.state != BreakerState::Closed)
+            .then(|| transition(&mut inner, BreakerState::Closed, "manual reset"));
+        drop(inner);
+        log_transition(change);
+    }
+
+    fn refresh(&self, inner: &mut Inner) -> Option<Transition> {
+        (inner.state == BreakerState::Open && inner.opened_at.elapsed() >= self.reset_timeout)
+            .then(|| transition(inner, BreakerState::HalfOpen, "reset timeout elapsed"))
+    }
+}
+
+/// Reopens the breaker if a trial call unwinds, so it isn't stuck half-open.
+struct TrialGuard<'a> {
+    breaker: &'a CircuitBreaker,
+}
+
+impl Drop for TrialGuard<'_> {
+    fn drop(&mut self) {
+        // Not logged: logging can escalate into a second panic while unwinding.
+        let mut inner = self
+            .breaker
+            .inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        inner.trial_running = false;
+        inner.failures = inner.failures.saturating_add(1);
+        inner.state = BreakerState::Open;
+        inner.opened_at = Instant::now();
+    }
+}
+
+/// A state change to log once the lock is released.
+type Transition = (LogLevel, String);
+
+/// Applies a state change and returns its log record. Callers log it after
+/// releasing the lock, so log escalation can't poison or deadlock the breaker.
+fn transition(inner: &mut Inner, to: BreakerState, reason: &str) -> Transition {
+    let level = match to {
+        BreakerState::Open => LogLevel::Warn,
+        BreakerState::Closed | BreakerState::HalfOpen => LogLevel::Info,
+    };
+    let message = format!("circuit breaker {:?} -> {:?} ({reason})", inner.state, to);
+    if to == BreakerState::Open {
+        inner.opened_at = Instant::now();
+    }
+    inner.state = to;
+    (level, message)
+}
+
+fn log_transition(change: Option<Transition>) {
+    if let Some((level, message)) = change {
+        log_sync(level, &message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        panic::{AssertUnwindSafe, catch_unwind},
+        sync::Arc,
+    };
+
+    use crate::logger::{Action, Log, lock_logger_for_test};
+
+    fn half_open_breaker() -> CircuitBreaker {
+        let breaker = CircuitBreaker::new(1, Duration::ZERO);
+        let _ = breaker.call(|| Err::<(), _>("down"));
+        assert_eq!(breaker.state(), BreakerState::HalfOpen);
+        breaker
+    }
+
+    #[test]
+    fn panicking_trial_reopens_instead_of_sticking_half_open() {
+        let _logger = lock_logger_for_test();
+        let breaker = half_open_breaker();
+        let panicked = catch_unwind(AssertUnwindSafe(|| {
+            breaker.call(|| -> Result<(), ()> { panic!("trial blew up") })
+        }));
+        assert!(panicked.is_err());
+        // Zero reset timeout: the reopened breaker is immediately half-open again.
+        assert_eq!(breaker.state(), BreakerState::HalfOpen);
+        assert_eq!(breaker.call(|| Ok::<_, ()>(1)), Ok(1));
+        assert_eq!(breaker.state(), BreakerState::Closed);
+    }
+
+    #[test]
+    fn escalation_callbacks_can_reenter_the_breaker() {
+        let _logger = lock_logger_for_test();
+        let breaker = Arc::new(CircuitBreaker::new(1, Duration::from_secs(60)));
+        let observed = Arc::clone(&breaker);
+        Log::escalate_at(
+            LogLevel::Warn,
+            Action::Callback(Arc::new(move |_, message| {
+                if message.starts_with("circuit breaker") {
+                    observed.state();
+                }
+            })),
+        );
+        let _ = breaker.call(|| Err::<(), _>("down"));
+        Log::clear_escalation();
+        assert_eq!(breaker.state(), BreakerState::Open);
+    }
+}
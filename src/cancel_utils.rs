@@ -0,0 +1,269 @@
+use std::{
+    fmt,
+    sync::{
+        Arc, Mutex, Weak,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+#[cfg(feature = "async")]
+use tokio::sync::Notify;
+
+type Callback = Box<dyn FnOnce() + Send>;
+
+#[derive(Default)]
+struct Listeners {
+    next_id: u64,
+    callbacks: Vec<(u64, Callback)>,
+    /// Kept alive so callbacks on dropped children still run. Children nobody
+    /// can observe any more are pruned on insert.
+    children: Vec<Arc<Inner>>,
+}
+
+impl Listeners {
+    fn push(&mut self, callback: Callback) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.callbacks.push((id, callback));
+        id
+    }
+
+    fn is_empty(&self) -> bool {
+        self.callbacks.is_empty() && self.children.is_empty()
+    }
+}
+
+struct Inner {
+    cancelled: AtomicBool,
+    listeners: Mutex<Listeners>,
+    #[cfg(feature = "async")]
+    notify: Notify,
+}
+
+/// Shared flag for stopping long-running work cooperatively.
+///
+/// Clones share the same state. Cancelling is one-way and idempotent; work is
+/// expected to check [`is_cancelled`](Self::is_cancelled) or react through
+/// [`on_cancel`](Self::on_cancel).
+#[derive(Clone)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+impl CancellationToken {
+    /// Creates a token that is not cancelled.
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                cancelled: AtomicBool::new(false),
+                listeners: Mutex::new(Listeners::default()),
+                #[cfg(feature = "async")]
+                notify: Notify::new(),
+            }),
+        }
+    }
+
+    /// Cancels the token and runs its callbacks. Later calls do nothing.
+    pub fn cancel(&self) {
+        let listeners = {
+            let mut listeners = self.inner.listeners.lock().unwrap();
+            if self.inner.cancelled.swap(true, Ordering::AcqRel) {
+                return;
+            }
+            std::mem::take(&mut *listeners)
+        };
+        #[cfg(feature = "async")]
+        self.inner.notify.notify_waiters();
+        for child in listeners.children {
+            CancellationToken { inner: child }.cancel();
+        }
+        for (_, callback) in listeners.callbacks {
+            callback();
+        }
+    }
+
+    /// Returns `true` once the token (or one of its parents) was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Creates a token that is cancelled along with `self`, but can also be cancelled on its own.
+    pub fn child_token(&self) -> Self {
+        let child = Self::new();
+        {
+            let mut listeners = self.inner.listeners.lock().unwrap();
+            if !self.is_cancelled() {
+                listeners.children.retain(|child| !is_unobservable(child));
+                listeners.children.push(Arc::clone(&child.inner));
+                return child;
+            }
+        }
+        child.cancel();
+        child
+    }
+
+    /// Runs `f` when the token is cancelled, or right away if it already is.
+    ///
+    /// Callbacks run on the thread that calls [`cancel`](Self::cancel) and stay
+    /// registered until then; use [`register`](Self::register) for callbacks
+    /// that should go away earlier.
+    pub fn on_cancel<F: FnOnce() + Send + 'static>(&self, f: F) {
+        self.register(f).forget();
+    }
+
+    /// Same as [`on_cancel`](Self::on_cancel), but the callback is removed
+    /// again when the returned registration is dropped.
+    pub fn register<F: FnOnce() + Send + 'static>(&self, f: F) -> CancelRegistration {
+        {
+            let mut listeners = self.inner.listeners.lock().unwrap();
+            if !self.is_cancelled() {
+                let id = listeners.push(Box::new(f));
+                return CancelRegistration {
+                    token: Arc::downgrade(&self.inner),
+                    id: Some(id),
+                };
+            }
+        }
+        f();
+        CancelRegistration {
+            token: Weak::new(),
+            id: None,
+        }
+    }
+
+    /// Waits until the token is cancelled.
+    #[cfg(feature = "async")]
+    pub async fn cancelled(&self) {
+        loop {
+            let mut notified = std::pin::pin!(self.inner.notify.notified());
+            // Register before checking so a cancel in between isn't missed.
+            notified.as_mut().enable();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Keeps a callback registered with [`CancellationToken::register`] until dropped.
+#[derive(Debug)]
+#[must_use = "dropping the registration removes the callback"]
+pub struct CancelRegistration {
+    token: Weak<Inner>,
+    id: Option<u64>,
+}
+
+impl CancelRegistration {
+    /// Keeps the callback registered for the token's whole lifetime.
+    pub fn forget(mut self) {
+        self.id = None;
+    }
+}
+
+impl Drop for CancelRegistration {
+    fn drop(&mut self) {
+        let (Some(id), Some(inner)) = (self.id, self.token.upgrade()) else {
+            return;
+        };
+        inner
+            .listeners
+            .lock()
+            .unwrap()
+            .callbacks
+            .retain(|(registered, _)| *registered != id);
+    }
+}
+
+/// Returns `true` if cancelling `child` could no longer have any effect: it is
+/// already cancelled, or only its parent holds it and nothing listens to it.
+fn is_unobservable(child: &Arc<Inner>) -> bool {
+    child.cancelled.load(Ordering::Acquire)
+        || (Arc::strong_count(child) == 1
+            && child
+                .listeners
+                .try_lock()
+                .is_ok_and(|listeners| listeners.is_empty()))
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dropped_children_are_not_retained() {
+        let root = CancellationToken::new();
+        for _ in 0..10_000 {
+            drop(root.child_token());
+        }
+        let kept = root.child_token();
+        assert_eq!(root.inner.listeners.lock().unwrap().children.len(), 1);
+        root.cancel();
+        assert!(kept.is_cancelled());
+    }
+
+    #[test]
+    fn dropped_children_with_callbacks_still_fire() {
+        use std::sync::atomic::AtomicUsize;
+
+        let root = CancellationToken::new();
+        let fired = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&fired);
+        root.child_token().on_cancel(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let grandchild_counter = Arc::clone(&fired);
+        root.child_token().child_token().on_cancel(move || {
+            grandchild_counter.fetch_add(1, Ordering::SeqCst);
+        });
+        // Registering more children must not prune the listening ones.
+        drop(root.child_token());
+        root.cancel();
+        assert_eq!(fired.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn dropped_registrations_remove_their_callback() {
+        let token = CancellationToken::new();
+        for _ in 0..100 {
+            drop(token.register(|| panic!("removed callback ran")));
+        }
+        let kept = token.register(|| {});
+        assert_eq!(token.inner.listeners.lock().unwrap().callbacks.len(), 1);
+        token.cancel();
+        drop(kept);
+    }
+
+    #[test]
+    fn cancelled_children_are_pruned() {
+        let root = CancellationToken::new();
+        let child = root.child_token();
+        child.cancel();
+        let _kept = root.child_token();
+        assert_eq!(root.inner.listeners.lock().unwrap().children.len(), 1);
+    }
+
+    #[test]
+    fn children_of_cancelled_tokens_start_cancelled() {
+        let root = CancellationToken::new();
+        let child = root.child_token();
+        let grandchild = child.child_token();
+        root.cancel();
+        assert!(grandchild.is_cancelled());
+        assert!(root.child_token().is_cancelled());
+    }
+}
@@ -0,0 +1,136 @@
+use std::fmt::Display;
+
+use crate::term_utils::width::WidthUtils;
+
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+const PARTIAL_BLOCKS: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+const HISTOGRAM_WIDTH: usize = 40;
+
+/// One-line charts for numeric slices.
+pub trait SparklineUtils {
+    /// Renders the values as block characters scaled between their min and max,
+    /// e.g. `"▁▃▅▇"`. Non-finite values render as spaces.
+    fn sparkline(&self) -> String;
+}
+
+impl<T: Copy + Into<f64>> SparklineUtils for [T] {
+    fn sparkline(&self) -> String {
+        let values: Vec<f64> = self.iter().map(|&v| v.into()).collect();
+        let Some((min, max)) = finite_bounds(&values) else {
+            return " ".repeat(values.len());
+        };
+        let top = (SPARKS.len() - 1) as f64;
+        values
+            .iter()
+            .map(|&v| {
+                if !v.is_finite() {
+                    ' '
+                } else if max == min {
+                    SPARKS[0]
+                } else {
+                    SPARKS[((v - min) / (max - min) * top).round() as usize]
+                }
+            })
+            .collect()
+    }
+}
+
+/// Horizontal bar charts for labeled values.
+pub trait BarChartUtils {
+    /// Renders one `label │████▌ value` line per pair, with the largest value
+    /// filling `width` cells. Negative and non-finite values draw no bar.
+    fn bar_chart(&self, width: usize) -> String;
+}
+
+impl<L: Display, T: Copy + Into<f64>> BarChartUtils for [(L, T)] {
+    fn bar_chart(&self, width: usize) -> String {
+        let rows: Vec<(String, f64)> = self
+            .iter()
+            .map(|(label, value)| (label.to_string(), (*value).into()))
+            .collect();
+        let label_width = rows
+            .iter()
+            .map(|(label, _)| label.display_width())
+            .max()
+            .unwrap_or(0);
+        let max = rows
+            .iter()
+            .map(|&(_, v)| v)
+            .filter(|v| v.is_finite())
+            .fold(0.0, f64::max);
+
+        let mut out = String::new();
+        for (label, value) in &rows {
+            let cells = if max > 0.0 && value.is_finite() && *value > 0.0 {
+                value / max * width as f64
+            } else {
+                0.0
+            };
+            let bar = bar(cells);
+            let fill = width.saturating_sub(bar.chars().count());
+            out.push_str(&format!(
+                "{} │{bar}{} {value}\n",
+                label.pad_display(label_width),
+                " ".repeat(fill)
+            ));
+        }
+        out
+    }
+}
+
+/// Buckets `values` into `buckets` equal ranges and renders the counts as a bar chart.
+///
+/// Each line is labeled with its `[low, high)` range; the last range includes the max.
+/// Non-finite values are skipped.
+pub fn histogram<T: Copy + Into<f64>>(values: &[T], buckets: usize) -> String {
+    let values: Vec<f64> = values
+        .iter()
+        .map(|&v| v.into())
+        .filter(|v| v.is_finite())
+        .collect();
+    let Some((min, max)) = finite_bounds(&values) else {
+        return String::new();
+    };
+    // All-equal values can't be split into ranges.
+    let buckets = if max > min { buckets.max(1) } else { 1 };
+    let step = (max - min) / buckets as f64;
+
+    let mut counts = vec![0u32; buckets];
+    for v in &values {
+        let bucket = if step > 0.0 { (v - min) / step } else { 0.0 };
+        counts[(bucket as usize).min(buckets - 1)] += 1;
+    }
+
+    let rows: Vec<(String, u32)> = counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| {
+            let low = min + step * i as f64;
+            let high = min + step * (i + 1) as f64;
+            let close = if i + 1 == buckets { ']' } else { ')' };
+            (format!("[{low:.2}, {high:.2}{close}"), count)
+        })
+        .collect();
+    rows.bar_chart(HISTOGRAM_WIDTH)
+}
+
+fn finite_bounds(values: &[f64]) -> Option<(f64, f64)> {
+    values
+        .iter()
+        .filter(|v| v.is_finite())
+        .fold(None, |bounds, &v| match bounds {
+            None => Some((v, v)),
+            Some((min, max)) => Some((f64::min(min, v), f64::max(max, v))),
+        })
+}
+
+/// Draws `cells` cells of bar, using eighth blocks for the fractional part.
+fn bar(cells: f64) -> String {
+    let eighths = (cells * 8.0).round() as usize;
+    let mut bar = "█".repeat(eighths / 8);
+    let partial = eighths % 8;
+    if partial > 0 {
+        bar.push(PARTIAL_BLOCKS[partial - 1]);
+    }
+    bar
+}
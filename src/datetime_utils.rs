@@ -0,0 +1,185 @@
+use std::{
+    fmt,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub(crate) const SECS_PER_DAY: i64 = 86_400;
+
+/// Calendar components of a point in time, in UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    pub year: i64,
+    pub month: u32,
+    pub day: u32,
+    pub hour: u32,
+    pub minute: u32,
+    pub second: u32,
+    pub nanosecond: u32,
+}
+
+impl DateTime {
+    /// Splits a `SystemTime` into UTC calendar components.
+    pub fn from_system_time(time: SystemTime) -> Self {
+        let (secs, nanosecond) = unix_parts(time);
+        let (year, month, day) = civil_from_days(secs.div_euclid(SECS_PER_DAY));
+        let secs_of_day = secs.rem_euclid(SECS_PER_DAY) as u32;
+        Self {
+            year,
+            month,
+            day,
+            hour: secs_of_day / 3600,
+            minute: secs_of_day % 3600 / 60,
+            second: secs_of_day % 60,
+            nanosecond,
+        }
+    }
+
+    /// Converts the components back into a `SystemTime`.
+    pub fn to_system_time(&self) -> SystemTime {
+        let days = days_from_civil(self.year, self.month, self.day);
+        let secs = days * SECS_PER_DAY
+            + i64::from(self.hour) * 3600
+            + i64::from(self.minute) * 60
+            + i64::from(self.second);
+        from_unix_secs(secs) + Duration::from_nanos(u64::from(self.nanosecond))
+    }
+
+    /// Returns the day of the week, from `0` (Sunday) to `6` (Saturday).
+    pub fn weekday(&self) -> u32 {
+        (days_from_civil(self.year, self.month, self.day) + 4).rem_euclid(7) as u32
+    }
+
+    /// Formats as `YYYY-MM-DDThh:mm:ssZ`.
+    pub fn format_iso8601(&self) -> String {
+        self.format("YYYY-MM-DDThh:mm:ssZ")
+    }
+
+    /// Formats using a pattern of `YYYY`, `MM`, `DD`, `hh`, `mm` and `ss` tokens.
+    ///
+    /// Everything else in the pattern is copied as-is.
+    pub fn format(&self, pattern: &str) -> String {
+        use fmt::Write;
+
+        let mut out = String::with_capacity(pattern.len() + 4);
+        let mut rest = pattern;
+        while let Some(c) = rest.chars().next() {
+            let token = ["YYYY", "MM", "DD", "hh", "mm", "ss"]
+                .into_iter()
+                .find(|token| rest.starts_with(token));
+            let Some(token) = token else {
+                out.push(c);
+                rest = &rest[c.len_utf8()..];
+                continue;
+            };
+            // Writing to a `String` can't fail.
+            let _ = match token {
+                "YYYY" => write!(out, "{:04}", self.year),
+                "MM" => write!(out, "{:02}", self.month),
+                "DD" => write!(out, "{:02}", self.day),
+                "hh" => write!(out, "{:02}", self.hour),
+                "mm" => write!(out, "{:02}", self.minute),
+                _ => write!(out, "{:02}", self.second),
+            };
+            rest = &rest[token.len()..];
+        }
+        out
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format_iso8601())
+    }
+}
+
+/// Calendar helpers for `SystemTime`, without pulling in a date crate.
+pub trait DateTimeUtils {
+    /// Returns the UTC calendar components.
+    fn to_datetime(&self) -> DateTime;
+
+    /// Formats as `YYYY-MM-DDThh:mm:ssZ`.
+    fn format_iso8601(&self) -> String;
+
+    /// Formats using `YYYY`, `MM`, `DD`, `hh`, `mm` and `ss` tokens, e.g. `"YYYY-MM-DD hh:mm"`.
+    fn format(&self, pattern: &str) -> String;
+
+    /// Returns the number of UTC calendar days from `self` to `other` (negative if earlier).
+    fn days_between(&self, other: &SystemTime) -> i64;
+}
+
+impl DateTimeUtils for SystemTime {
+    fn to_datetime(&self) -> DateTime {
+        DateTime::from_system_time(*self)
+    }
+    fn format_iso8601(&self) -> String {
+        self.to_datetime().format_iso8601()
+    }
+    fn format(&self, pattern: &str) -> String {
+        self.to_datetime().format(pattern)
+    }
+    fn days_between(&self, other: &SystemTime) -> i64 {
+        let days = |time| unix_parts(time).0.div_euclid(SECS_PER_DAY);
+        days(*other) - days(*self)
+    }
+}
+
+/// Splits a `SystemTime` into whole seconds since the epoch (floored) and nanoseconds.
+pub(crate) fn unix_parts(time: SystemTime) -> (i64, u32) {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(d) => (d.as_secs() as i64, d.subsec_nanos()),
+        Err(e) => {
+            let d = e.duration();
+            match d.subsec_nanos() {
+                0 => (-(d.as_secs() as i64), 0),
+                nanos => (-(d.as_secs() as i64) - 1, 1_000_000_000 - nanos),
+            }
+        }
+    }
+}
+
+/// Builds a `SystemTime` from epoch seconds and nanoseconds, or `None` if
+/// `nanos` isn't below one second or the time isn't representable.
+pub(crate) fn checked_from_unix(secs: i64, nanos: u32) -> Option<SystemTime> {
+    if nanos >= 1_000_000_000 {
+        return None;
+    }
+    let base = if secs >= 0 {
+        UNIX_EPOCH.checked_add(Duration::from_secs(secs as u64))?
+    } else {
+        UNIX_EPOCH.checked_sub(Duration::from_secs(secs.unsigned_abs()))?
+    };
+    base.checked_add(Duration::from_nanos(u64::from(nanos)))
+}
+
+pub(crate) fn from_unix_secs(secs: i64) -> SystemTime {
+    if secs >= 0 {
+        UNIX_EPOCH + Duration::from_secs(secs as u64)
+    } else {
+        UNIX_EPOCH - Duration::from_secs(secs.unsigned_abs())
+    }
+}
+
+/// Converts days since 1970-01-01 to `(year, month, day)`.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Converts `(year, month, day)` to days since 1970-01-01.
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = i64::from((month + 9) % 12);
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
@@ -0,0 +1,126 @@
+use std::fmt;
+
+/// Error returned when a name doesn't match any entry of an [`EnumMap`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownName {
+    name: String,
+    suggestion: Option<&'static str>,
+}
+
+impl UnknownName {
+    /// Returns the closest known name, if one is close enough to be a typo.
+    pub fn suggestion(&self) -> Option<&'static str> {
+        self.suggestion
+    }
+}
+
+impl fmt::Display for UnknownName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown name `{}`", self.name)?;
+        if let Some(suggestion) = self.suggestion {
+            write!(f, ", did you mean `{suggestion}`?")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for UnknownName {}
+
+/// Two-way mapping between names and enum-like values, without a derive.
+///
+/// `EnumMap::new().entry("red", Color::Red).entry("green", Color::Green)`
+#[derive(Debug, Clone)]
+pub struct EnumMap<T> {
+    entries: Vec<(&'static str, T)>,
+}
+
+impl<T: Clone + PartialEq> EnumMap<T> {
+    /// Creates an empty map.
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds a `name` for `value`. A value may have several names; the first is canonical.
+    pub fn entry(mut self, name: &'static str, value: T) -> Self {
+        self.entries.push((name, value));
+        self
+    }
+
+    /// Looks up `name`, ignoring ASCII case.
+    pub fn parse_name(&self, name: &str) -> Result<T, UnknownName> {
+        let name = name.trim();
+        self.entries
+            .iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.clone())
+            .ok_or_else(|| UnknownName {
+                name: name.to_string(),
+                suggestion: self.suggest(name),
+            })
+    }
+
+    /// Returns the canonical name of `value`, if it has one.
+    pub fn name_of(&self, value: &T) -> Option<&'static str> {
+        self.entries
+            .iter()
+            .find(|(_, known)| known == value)
+            .map(|(name, _)| *name)
+    }
+
+    /// Returns every known name in insertion order.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.entries.iter().map(|(name, _)| *name)
+    }
+
+    /// Picks the closest name within a third of its length (at least one edit).
+    fn suggest(&self, name: &str) -> Option<&'static str> {
+        let name = name.to_ascii_lowercase();
+        self.names()
+            .map(|known| (levenshtein(&name, &known.to_ascii_lowercase()), known))
+            .filter(|&(distance, known)| distance <= (known.chars().count() / 3).max(1))
+            .min_by_key(|&(distance, _)| distance)
+            .map(|(_, known)| known)
+    }
+}
+
+impl<T: Clone + PartialEq> Default for EnumMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Name round-tripping for enum-like types backed by an [`EnumMap`].
+///
+/// Implement `name_map` once, usually with a `static OnceLock`, and get
+/// `parse_name` and `name` for free.
+pub trait FromNameUtils: Clone + PartialEq + 'static {
+    /// Returns the mapping used by the other methods.
+    fn name_map() -> &'static EnumMap<Self>;
+
+    /// Parses a name, ignoring ASCII case, with a suggestion on typos.
+    fn parse_name(name: &str) -> Result<Self, UnknownName> {
+        Self::name_map().parse_name(name)
+    }
+
+    /// Returns the canonical name of `self`, if it has one.
+    fn name(&self) -> Option<&'static str> {
+        Self::name_map().name_of(self)
+    }
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut row = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            row[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(row[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut row);
+    }
+    prev[b.len()]
+}
@@ -0,0 +1,79 @@
+use std::{error::Error, process::ExitCode, sync::RwLock};
+
+use crate::logger::logs_for_exit;
+
+type Matcher = Box<dyn Fn(&(dyn Error + 'static)) -> bool + Send + Sync>;
+
+static EXIT_CODES: RwLock<Vec<(Matcher, u8)>> = RwLock::new(Vec::new());
+
+/// Exit code used when no registered error kind matches.
+pub const DEFAULT_EXIT_CODE: u8 = 1;
+
+/// Maps errors of type `E` anywhere in the error chain to `code`.
+pub fn register_exit_code<E: Error + 'static>(code: u8) {
+    register_exit_code_with(|err| err.is::<E>(), code);
+}
+
+/// Maps errors accepted by `matches` to `code`, e.g. a specific `io::ErrorKind`.
+///
+/// Registrations are checked in order for each error in the chain, starting
+/// from the outermost one; the first match wins.
+pub fn register_exit_code_with<F>(matches: F, code: u8)
+where
+    F: Fn(&(dyn Error + 'static)) -> bool + Send + Sync + 'static,
+{
+    EXIT_CODES.write().unwrap().push((Box::new(matches), code));
+}
+
+/// Returns the registered exit code for `err`, or [`DEFAULT_EXIT_CODE`].
+pub fn exit_code_for(err: &(dyn Error + 'static)) -> u8 {
+    let codes = EXIT_CODES.read().unwrap();
+    chain(err)
+        .find_map(|err| {
+            codes
+                .iter()
+                .find(|(matches, _)| matches(err))
+                .map(|(_, code)| *code)
+        })
+        .unwrap_or(DEFAULT_EXIT_CODE)
+}
+
+/// Formats `err` and its sources, one per line.
+pub fn format_error_chain(err: &(dyn Error + 'static)) -> String {
+    let mut out = format!("[FATAL]: {err}");
+    for source in chain(err).skip(1) {
+        out.push_str(&format!("\n  caused by: {source}"));
+    }
+    out
+}
+
+/// Runs a fallible main function and turns its outcome into an exit code.
+///
+/// On error, prints the stored `Log` entries and the error chain to stderr,
+/// then exits with the code registered for the error. If a background log
+/// writer is running, its pending records are flushed to its sink instead of
+/// being printed; when calling this from inside a Tokio runtime, await
+/// `Log::flush()` before returning the error.
+///
+/// `fn main() -> ExitCode { exit_utils::run(real_main) }`
+pub fn run<E, F>(main_fn: F) -> ExitCode
+where
+    E: Into<Box<dyn Error>>,
+    F: FnOnce() -> Result<(), E>,
+{
+    match main_fn() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            let err: Box<dyn Error> = err.into();
+            for log in logs_for_exit() {
+                eprintln!("{}", log);
+            }
+            eprintln!("{}", format_error_chain(err.as_ref()));
+            ExitCode::from(exit_code_for(err.as_ref()))
+        }
+    }
+}
+
+fn chain<'a>(err: &'a (dyn Error + 'static)) -> impl Iterator<Item = &'a (dyn Error + 'static)> {
+    std::iter::successors(Some(err), |&err| err.source())
+}
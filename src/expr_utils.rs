@@ -0,0 +1,208 @@
+use std::{
+    borrow::Borrow, collections::HashMap, fmt, hash::Hash, iter::Peekable, str::CharIndices,
+};
+
+/// Error returned when an expression can't be evaluated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExprError {
+    /// A character that doesn't fit the grammar, with its byte offset.
+    UnexpectedChar(char, usize),
+    /// The expression ended early, e.g. `"2 *"`.
+    UnexpectedEnd,
+    /// A number literal like `1.2.3` couldn't be parsed.
+    InvalidNumber(String),
+    /// A variable wasn't supplied.
+    UnknownVariable(String),
+    /// Division or remainder by zero.
+    DivisionByZero,
+    /// The result overflowed or isn't a real number.
+    NotFinite,
+    /// Parentheses or unary operators are nested deeper than the parser allows.
+    TooDeep,
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedChar(c, pos) => write!(f, "unexpected `{c}` at position {pos}"),
+            Self::UnexpectedEnd => f.write_str("unexpected end of expression"),
+            Self::InvalidNumber(n) => write!(f, "invalid number `{n}`"),
+            Self::UnknownVariable(name) => write!(f, "unknown variable `{name}`"),
+            Self::DivisionByZero => f.write_str("division by zero"),
+            Self::NotFinite => f.write_str("result is not a finite number"),
+            Self::TooDeep => write!(f, "expression nested deeper than {MAX_DEPTH} levels"),
+        }
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+/// Limits recursion so hostile input can't overflow the stack.
+const MAX_DEPTH: usize = 256;
+
+/// Evaluates an arithmetic expression like `"2 * (3 + 4) / 7"`.
+///
+/// Supports `+ - * / % ^`, unary minus and parentheses. `^` is right-associative
+/// and binds tighter than unary minus, so `-2^2` is `-4`.
+pub fn eval(expr: &str) -> Result<f64, ExprError> {
+    eval_with(expr, &HashMap::<&str, f64>::new())
+}
+
+/// Same as [`eval`], resolving identifiers like `limit * 2` from `vars`.
+pub fn eval_with<K>(expr: &str, vars: &HashMap<K, f64>) -> Result<f64, ExprError>
+where
+    K: Borrow<str> + Eq + Hash,
+{
+    let mut parser = Parser {
+        chars: expr.char_indices().peekable(),
+        lookup: &|name| vars.get(name).copied(),
+        depth: 0,
+    };
+    let value = parser.expr()?;
+    match parser.peek() {
+        Some((pos, c)) => Err(ExprError::UnexpectedChar(c, pos)),
+        None => Ok(value),
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<CharIndices<'a>>,
+    lookup: &'a dyn Fn(&str) -> Option<f64>,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn expr(&mut self) -> Result<f64, ExprError> {
+        let mut value = self.term()?;
+        while let Some((_, op @ ('+' | '-'))) = self.peek() {
+            self.chars.next();
+            let rhs = self.term()?;
+            value = finite(if op == '+' { value + rhs } else { value - rhs })?;
+        }
+        Ok(value)
+    }
+
+    fn term(&mut self) -> Result<f64, ExprError> {
+        let mut value = self.unary()?;
+        while let Some((_, op @ ('*' | '/' | '%'))) = self.peek() {
+            self.chars.next();
+            let rhs = self.unary()?;
+            if op != '*' && rhs == 0.0 {
+                return Err(ExprError::DivisionByZero);
+            }
+            value = finite(match op {
+                '*' => value * rhs,
+                '/' => value / rhs,
+                _ => value % rhs,
+            })?;
+        }
+        Ok(value)
+    }
+
+    /// Every recursive path (parentheses, unary signs, exponents) passes
+    /// through here, so this is where nesting depth is counted.
+    fn unary(&mut self) -> Result<f64, ExprError> {
+        if self.depth == MAX_DEPTH {
+            return Err(ExprError::TooDeep);
+        }
+        self.depth += 1;
+        let value = self.unary_inner();
+        self.depth -= 1;
+        value
+    }
+
+    fn unary_inner(&mut self) -> Result<f64, ExprError> {
+        match self.peek() {
+            Some((_, '-')) => {
+                self.chars.next();
+                Ok(-self.unary()?)
+            }
+            Some((_, '+')) => {
+                self.chars.next();
+                self.unary()
+            }
+            _ => self.power(),
+        }
+    }
+
+    fn power(&mut self) -> Result<f64, ExprError> {
+        let base = self.primary()?;
+        if let Some((_, '^')) = self.peek() {
+            self.chars.next();
+            let exponent = self.unary()?;
+            return finite(base.powf(exponent));
+        }
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<f64, ExprError> {
+        match self.peek() {
+            Some((_, '(')) => {
+                self.chars.next();
+                let value = self.expr()?;
+                match self.peek() {
+                    Some((_, ')')) => {
+                        self.chars.next();
+                        Ok(value)
+                    }
+                    Some((pos, c)) => Err(ExprError::UnexpectedChar(c, pos)),
+                    None => Err(ExprError::UnexpectedEnd),
+                }
+            }
+            Some((_, c)) if c.is_ascii_digit() || c == '.' => {
+                let number = self.take_while(|c| c.is_ascii_digit() || c == '.');
+                number.parse().map_err(|_| ExprError::InvalidNumber(number))
+            }
+            Some((_, c)) if c.is_alphabetic() || c == '_' => {
+                let name = self.take_while(|c| c.is_alphanumeric() || c == '_');
+                (self.lookup)(&name).ok_or(ExprError::UnknownVariable(name))
+            }
+            Some((pos, c)) => Err(ExprError::UnexpectedChar(c, pos)),
+            None => Err(ExprError::UnexpectedEnd),
+        }
+    }
+
+    /// Returns the next non-whitespace character without consuming it.
+    fn peek(&mut self) -> Option<(usize, char)> {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+        self.chars.peek().copied()
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> String {
+        let mut out = String::new();
+        while let Some((_, c)) = self.chars.next_if(|&(_, c)| f(c)) {
+            out.push(c);
+        }
+        out
+    }
+}
+
+fn finite(value: f64) -> Result<f64, ExprError> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(ExprError::NotFinite)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deep_nesting_is_an_error_not_a_stack_overflow() {
+        let parens = "(".repeat(200_000) + "1" + &")".repeat(200_000);
+        assert_eq!(eval(&parens), Err(ExprError::TooDeep));
+        let signs = "-".repeat(200_000) + "1";
+        assert_eq!(eval(&signs), Err(ExprError::TooDeep));
+        let powers = "2^".repeat(200_000) + "1";
+        assert_eq!(eval(&powers), Err(ExprError::TooDeep));
+    }
+
+    #[test]
+    fn moderate_nesting_still_evaluates() {
+        let parens = "(".repeat(100) + "1" + &")".repeat(100);
+        assert_eq!(eval(&parens), Ok(1.0));
+        assert_eq!(eval("--2^2"), Ok(4.0));
+    }
+}
@@ -12,30 +12,43 @@
 //!
 //! - **`Log`** – Simple in-memory logger with optional async support
 //!   – `.log_info()`, `.log_warn()`, `.print_logs()`, `.set_up_logger()`, `.clear()`
+//!   – `.log_scoped(LogLevel::Info, "db", "connected")` to tag records by source
+//!   – `.escalate_at(LogLevel::Error, Action::Exit(1))` for strict mode
+//!
+//! - **`Loggable`** – Log any `Display` value directly
+//!   – `.log()`, `.log_info()`, `.log_warn()`, `.log_error()`, `.log_debug()`
 //!
 //! - **`OptionUtils`** – More ergonomic handling of `Option<T>`
-//!   – `.if_some()`, `.or_default_with()`
+//!   – `.if_some()`, `.or_default_with()`, `.or_else_chain()`, `first_some()`
 //!
 //! - **`ResultUtils`** – Sugar methods for `Result<T, E>`
 //!   – `.if_ok()`, `.if_err()`, `.unwrap_or_exit()`
 //!
+//! - **`TimingUtils`** – Lightweight operation telemetry
+//!   – `timed(|| op())`, `.log_timing("label")`
+//!
 //! - **`BoolUtils`** – Conditionals made fancy
 //!   – `.toggle()`, `.not()`, `.then_val()`, `.if_true()`, `.if_false()`
 //!
-//! - **`VecUtils`** – Push conditionally into vectors
-//!   – `.push_if()`, `.push_if_with()`
+//! - **`VecUtils`** – Push conditionally into vectors and move elements between them
+//!   – `.push_if()`, `.push_if_with()`, `.drain_into()`, `.move_where()`, `.split_off_where()`
 //!
 //! - **`StrUtils`** – Extensions for `&str`
-//!   – `.contains_all()`, `.contains_any()`, `.to_title_case()`
+//!   – `.contains_all()`, `.contains_any()`, `.to_title_case()`, `.wrap()`
+//!   – `.lines_trimmed()`, `.non_empty_lines()`, `.map_lines()`, `.prefix_lines()`, `.line_at()`
+//!   – `.normalize_whitespace()`, `.remove_diacritics()`, `.to_comparable()`
 //!
 //! - **`MapUtils`** – `HashMap` helpers
 //!   – `.insert_if()`, `.get_or()`
 //!
+//! - **`CapacityUtils`** – Capacity telemetry for `Vec`, `String` and `HashMap`
+//!   – `.mem_report()`, `.shrink_if_wasteful(threshold)`
+//!
 //! - **`MemUtils`** – Reflection-like methods
 //!   – `.type_name()`, `.mem_size()`, `.view()`
 //!
-//! - **`IdentityUtils`** – Tap-style chaining
-//!   – `.tap()`
+//! - **`IdentityUtils`** – Tap-style chaining and Kotlin-style scoping
+//!   – `.tap()`, `.also()`, `.apply()`, `.let_owned()`
 //!
 //! - **`PanicUtils`** – Fatal exit helpers
 //!   – `.unwrap_or_exit()`
@@ -52,15 +65,72 @@
 //! - **`NumberUtils`** – Integer extensions
 //!   – `.is_even()`, `.is_odd()`
 //!
+//! - **`ScaleUtils`** – Range mapping for integers
+//!   – `.map_to_range()`, `.normalize(max)`, `.percent_change(from)`
+//!
 //! - **`IteratorUtils`** – Fallback logic for iterators
 //!   – `.find_map_or(f, fallback)`
 //!
+//! - **`StatsAccumulator`** / **`Ewma`** – Running statistics
+//!   – `.push()`, `.mean()`, `.stddev()`, `.min()`, `.max()`, `.p95()`, `with_reservoir(n)` for bounded memory
+//!
+//! - **`Schedule`** – Cron-like schedules without a cron crate
+//!   – `Schedule::parse("*/5 * * * *")`, `every().minutes(5)`, `.next_after()`, `.iter_upcoming()`
+//!
+//! - **`DateTimeUtils`** – Calendar formatting for `SystemTime`, no chrono needed
+//!   – `.format_iso8601()`, `.format("YYYY-MM-DD hh:mm")`, `.days_between()`
+//!
+//! - **`WeightedUtils`** / **`Rng`** – Std-only randomness for games and simulations
+//!   – `.pick_weighted(|i| i.weight)`, `roll("3d6+2")`, `normal_approx(mean, sd)`
+//!
+//! - **`Table`** – Text tables for CLI reports
+//!   – `Table::new(["Name", "Size"])`, `.add_row()`, `.align()`, `.max_width()`, `.render()`, `.render_markdown()`
+//!
+//! - **`Resolver`** – Dependency ordering for task runners
+//!   – `.add(item, deps)`, `.resolve()`, `.parallel_batches()`
+//!
+//! - **`exit_utils::run`** – Fallible `main` with readable error reports
+//!   – error chain, pending logs, and exit codes via `register_exit_code::<E>(code)`
+//!
+//! - **`WidthUtils`** – ANSI-aware string width in `term_utils::width`
+//!   – `.display_width()`, `.pad_display(width)`, `.strip_ansi()`, `align_columns(rows)`
+//!
+//! - **`CircuitBreaker`** – Fail fast on a flaky dependency
+//!   – `CircuitBreaker::new(threshold, reset_timeout)`, `.call(f)`, `.state()`
+//!
+//! - **`ShellUtils`** – Shell-style word splitting and quoting
+//!   – `.split_shell_words()`, `.shell_quote()`
+//!
+//! - **`expr_utils::eval`** – Checked arithmetic expressions
+//!   – `eval("2 * (3 + 4) / 7")`, `eval_with("limit * 2", &vars)`
+//!
+//! - **`watch_utils::watch`** – Std-only polling file watcher
+//!   – `watch(path, interval, |p| reload(p))`, stops when the guard is dropped or via `watch_until(.., &token, ..)`
+//!
+//! - **`Progress`** – Progress bars on a terminal, `Log` entries elsewhere
+//!   – `.inc()`, `.set_message()`, `.finish()`, `iter.with_progress("indexing")`
+//!
+//! - **`ByteSize`** – Byte counts with parsing, math and `Display`
+//!   – `"1.5 GiB".parse::<ByteSize>()`, `ByteSize::mib(512) + ByteSize::kib(1)`, `{}` / `{:#}`
+//!
+//! - **`TypeRegistry`** – Thread-safe service locator for wiring apps together
+//!   – `.provide()`, `.get::<T>()`, `.get_or_init()`, `.child()`
+//!
+//! - **`SparklineUtils`** / **`BarChartUtils`** – Plain-text charts for logs and terminals
+//!   – `.sparkline()`, `.bar_chart(width)`, `histogram(values, buckets)`
+//!
+//! - **`CancellationToken`** – Cooperative cancellation shared across threads
+//!   – `.cancel()`, `.is_cancelled()`, `.child_token()`, `.on_cancel()`, `.register()`, `.cancelled().await`
+//!
+//! - **`EnumMap`** / **`FromNameUtils`** – Derive-free enum name round-tripping
+//!   – `EnumMap::new().entry("red", Color::Red)`, `.parse_name()` with typo suggestions, `.name_of()`
+//!
 //! ---
 //!
 //! ## Quick Example
 //!
 //! ```rust
-//! use utilz_rs::*;
+//! use utilz_rs::prelude::*;
 //!
 //! let value = Some("hi");
 //! value.if_some(|v| println!("Got: {v}"));
@@ -86,18 +156,51 @@
 //!
 //! _Use what you want, ignore the rest. No macros. No surprises._
 
-use std::{any::type_name, collections::HashMap, hash::Hash, time::Duration};
+use std::{
+    any::type_name,
+    collections::HashMap,
+    fmt::Display,
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+use crate::logger::{LogLevel, log_sync};
 
 pub mod bool_utils;
+pub mod breaker_utils;
+pub mod cancel_utils;
+pub mod chart_utils;
+pub mod datetime_utils;
+pub mod enum_utils;
+pub mod exit_utils;
+pub mod expr_utils;
 pub mod logger;
 pub mod option_utils;
+pub mod progress_utils;
+pub mod rand_utils;
+pub mod registry_utils;
+pub mod schedule_utils;
+pub mod shell_utils;
+pub mod size_utils;
+pub mod stats_utils;
 pub mod str_utils;
+pub mod table_utils;
+pub mod term_utils;
+pub mod topo_utils;
+pub mod watch_utils;
 
 pub mod prelude {
     pub use crate::bool_utils::*;
+    pub use crate::chart_utils::{BarChartUtils, SparklineUtils};
+    pub use crate::datetime_utils::DateTimeUtils;
+    pub use crate::enum_utils::FromNameUtils;
     pub use crate::logger::Loggable;
     pub use crate::option_utils::*;
+    pub use crate::progress_utils::ProgressIteratorUtils;
+    pub use crate::rand_utils::WeightedUtils;
+    pub use crate::shell_utils::ShellUtils;
     pub use crate::str_utils::*;
+    pub use crate::term_utils::width::WidthUtils;
     pub use crate::*;
 }
 
@@ -156,6 +259,15 @@ pub trait VecUtils<T> {
 
     /// Lazily evaluates and pushes the value if `cond` is `true`.
     fn push_if_with<F: FnOnce() -> T>(&mut self, cond: bool, f: F);
+
+    /// Moves every element to the end of `other`, leaving `self` empty.
+    fn drain_into(&mut self, other: &mut Vec<T>);
+
+    /// Moves elements matching `pred` to the end of `other`. Both keep their order.
+    fn move_where<F: FnMut(&T) -> bool>(&mut self, pred: F, other: &mut Vec<T>);
+
+    /// Removes and returns the elements matching `pred`. Both keep their order.
+    fn split_off_where<F: FnMut(&T) -> bool>(&mut self, pred: F) -> Vec<T>;
 }
 
 impl<T> VecUtils<T> for Vec<T> {
@@ -169,6 +281,17 @@ impl<T> VecUtils<T> for Vec<T> {
             self.push(f());
         }
     }
+    fn drain_into(&mut self, other: &mut Vec<T>) {
+        other.append(self);
+    }
+    fn move_where<F: FnMut(&T) -> bool>(&mut self, mut pred: F, other: &mut Vec<T>) {
+        other.extend(self.extract_if(.., |item| pred(item)));
+    }
+    fn split_off_where<F: FnMut(&T) -> bool>(&mut self, pred: F) -> Vec<T> {
+        let mut matched = Vec::new();
+        self.move_where(pred, &mut matched);
+        matched
+    }
 }
 
 pub trait MapUtils<K, V> {
@@ -188,6 +311,64 @@ impl<K: Eq + Hash, V> MapUtils<K, V> for HashMap<K, V> {
     }
 }
 
+/// Capacity telemetry for long-lived collections.
+pub trait CapacityUtils {
+    /// Returns `(len, capacity, approx_bytes)`, where `approx_bytes` is the heap
+    /// space reserved for elements.
+    fn mem_report(&self) -> (usize, usize, usize);
+
+    /// Shrinks to fit if capacity exceeds `len * threshold`. Returns `true` if
+    /// the capacity actually went down.
+    ///
+    /// `threshold` is clamped to at least `1.0`, since capacity never drops below `len`.
+    fn shrink_if_wasteful(&mut self, threshold: f64) -> bool;
+}
+
+impl<T> CapacityUtils for Vec<T> {
+    fn mem_report(&self) -> (usize, usize, usize) {
+        let bytes = self.capacity() * std::mem::size_of::<T>();
+        (self.len(), self.capacity(), bytes)
+    }
+    fn shrink_if_wasteful(&mut self, threshold: f64) -> bool {
+        let before = self.capacity();
+        if is_wasteful(self.len(), before, threshold) {
+            self.shrink_to_fit();
+        }
+        self.capacity() < before
+    }
+}
+
+impl CapacityUtils for String {
+    fn mem_report(&self) -> (usize, usize, usize) {
+        (self.len(), self.capacity(), self.capacity())
+    }
+    fn shrink_if_wasteful(&mut self, threshold: f64) -> bool {
+        let before = self.capacity();
+        if is_wasteful(self.len(), before, threshold) {
+            self.shrink_to_fit();
+        }
+        self.capacity() < before
+    }
+}
+
+impl<K: Eq + Hash, V> CapacityUtils for HashMap<K, V> {
+    fn mem_report(&self) -> (usize, usize, usize) {
+        let bytes = self.capacity() * std::mem::size_of::<(K, V)>();
+        (self.len(), self.capacity(), bytes)
+    }
+    fn shrink_if_wasteful(&mut self, threshold: f64) -> bool {
+        let before = self.capacity();
+        if is_wasteful(self.len(), before, threshold) {
+            self.shrink_to_fit();
+        }
+        self.capacity() < before
+    }
+}
+
+fn is_wasteful(len: usize, capacity: usize, threshold: f64) -> bool {
+    capacity > 0 && capacity as f64 > len as f64 * threshold.max(1.0)
+}
+
 pub trait ResultUtils<T, E> {
     fn if_ok<F: FnOnce(&T)>(self, f: F) -> Self;
     fn if_err<F: FnOnce(&E)>(self, f: F) -> Self;
@@ -209,6 +390,37 @@ impl<T, E: std::fmt::Debug> ResultUtils<T, E> for Result<T, E> {
     }
 }
 
+/// Runs a fallible operation and returns its result with the time it took.
+///
+/// `let (res, took) = timed(|| fetch());`
+pub fn timed<T, E, F: FnOnce() -> Result<T, E>>(f: F) -> (Result<T, E>, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Telemetry for the output of [`timed`].
+pub trait TimingUtils<T, E> {
+    /// Logs the label, duration and outcome, then returns the result.
+    ///
+    /// `Ok` is logged at `Info`, `Err` at `Error` together with the error.
+    fn log_timing(self, label: &str) -> Result<T, E>;
+}
+
+impl<T, E: Display> TimingUtils<T, E> for (Result<T, E>, Duration) {
+    fn log_timing(self, label: &str) -> Result<T, E> {
+        let (result, took) = self;
+        match &result {
+            Ok(_) => log_sync(LogLevel::Info, &format!("{label} ok in {took:?}")),
+            Err(err) => log_sync(
+                LogLevel::Error,
+                &format!("{label} failed after {took:?}: {err}"),
+            ),
+        }
+        result
+    }
+}
+
 /// Pretty-formatting for `Duration`.
 pub trait DurationUtils {
     /// Returns a formatted string like `"1h 20m 5s"`.
@@ -237,6 +449,17 @@ impl<I: Iterator> IteratorUtils for I {
 
 pub trait IdentityUtils: Sized {
     fn tap<F: FnOnce(&Self)>(self, f: F) -> Self;
+
+    /// Runs `f` with mutable access to a borrowed value and returns it for chaining.
+    fn also<F: FnOnce(&mut Self)>(&mut self, f: F) -> &mut Self;
+
+    /// Mutates an owned value in place and returns it, builder-style.
+    ///
+    /// `Config::default().apply(|c| { c.port = 1; })`
+    fn apply<F: FnOnce(&mut Self)>(self, f: F) -> Self;
+
+    /// Passes the value into `f` and returns whatever `f` produces.
+    fn let_owned<R, F: FnOnce(Self) -> R>(self, f: F) -> R;
 }
 
 impl<T> IdentityUtils for T {
@@ -244,6 +467,20 @@ impl<T> IdentityUtils for T {
         f(&self);
         self
     }
+
+    fn also<F: FnOnce(&mut Self)>(&mut self, f: F) -> &mut Self {
+        f(self);
+        self
+    }
+
+    fn apply<F: FnOnce(&mut Self)>(mut self, f: F) -> Self {
+        f(&mut self);
+        self
+    }
+
+    fn let_owned<R, F: FnOnce(Self) -> R>(self, f: F) -> R {
+        f(self)
+    }
 }
 
 /// Helpers to panic or exit cleanly with messages.
@@ -296,6 +533,51 @@ where
     }
 }
 
+/// Range mapping and normalization for integers.
+pub trait ScaleUtils: Sized {
+    /// Linearly maps `self` from `src_min..=src_max` onto `dst_min..=dst_max`.
+    ///
+    /// Values outside the source range are clamped to it and the result is
+    /// truncated toward `dst_min`. An empty source range maps to `dst_min`.
+    fn map_to_range(self, src_min: Self, src_max: Self, dst_min: Self, dst_max: Self) -> Self;
+
+    /// Returns `self / max` as a float, or `0.0` if `max` is zero.
+    fn normalize(self, max: Self) -> f64;
+
+    /// Returns the change from `from` to `self` in percent, or `None` if `from` is zero.
+    fn percent_change(self, from: Self) -> Option<f64>;
+}
+
+macro_rules! impl_scale_utils {
+    ($($t:ty),*) => {$(
+        impl ScaleUtils for $t {
+            fn map_to_range(self, src_min: Self, src_max: Self, dst_min: Self, dst_max: Self) -> Self {
+                if src_min == src_max {
+                    return dst_min;
+                }
+                let value = self.clamp(src_min.min(src_max), src_min.max(src_max)) as i128;
+                let (src_min, src_max) = (src_min as i128, src_max as i128);
+                let (dst_min, dst_max) = (dst_min as i128, dst_max as i128);
+                let (offset, span, src_span) = (value - src_min, dst_max - dst_min, src_max - src_min);
+                let scaled = match offset.checked_mul(span) {
+                    Some(product) => product / src_span,
+                    // Only full-width 64-bit ranges get here; scale through `f64`.
+                    None => (offset as f64 * span as f64 / src_span as f64) as i128,
+                };
+                (dst_min + scaled).clamp(dst_min.min(dst_max), dst_min.max(dst_max)) as Self
+            }
+            fn normalize(self, max: Self) -> f64 {
+                if max == 0 { 0.0 } else { self as f64 / max as f64 }
+            }
+            fn percent_change(self, from: Self) -> Option<f64> {
+                (from != 0).then(|| (self as f64 - from as f64) / (from as f64).abs() * 100.0)
+            }
+        }
+    )*};
+}
+
+impl_scale_utils!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
 pub trait UNumberUtils {
     #[must_use]
     fn is_even(&self) -> bool;
@@ -304,10 +586,10 @@ pub trait UNumberUtils {
 }
 impl UNumberUtils for u32 {
     fn is_even(&self) -> bool {
-        self % 2 == 0
+        self.is_multiple_of(2)
     }
     fn is_odd(&self) -> bool {
-        self % 2 != 0
+        !self.is_multiple_of(2)
     }
 }
 
@@ -320,3 +602,47 @@ impl BitwiseUtils for u32 {
         self ^ rhs
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_to_range_handles_full_width_ranges() {
+        assert_eq!(u64::MAX.map_to_range(0, u64::MAX, 0, u64::MAX), u64::MAX);
+        assert_eq!(0u64.map_to_range(0, u64::MAX, 0, u64::MAX), 0);
+        assert_eq!(
+            i64::MAX.map_to_range(i64::MIN, i64::MAX, i64::MIN, i64::MAX),
+            i64::MAX
+        );
+        assert_eq!(
+            i64::MIN.map_to_range(i64::MIN, i64::MAX, i64::MAX, i64::MIN),
+            i64::MAX
+        );
+        assert_eq!(usize::MAX.map_to_range(0, usize::MAX, 0, 100), 100);
+        assert_eq!(50u64.map_to_range(0, 100, 0, u64::MAX), u64::MAX / 2);
+    }
+
+    #[test]
+    fn shrink_if_wasteful_reports_only_real_shrinks() {
+        let mut v: Vec<u8> = Vec::with_capacity(64);
+        v.extend([1, 2, 3]);
+        assert!(v.shrink_if_wasteful(0.5));
+        assert!(!v.shrink_if_wasteful(0.5));
+        assert!(!v.shrink_if_wasteful(0.0));
+
+        let mut s = String::with_capacity(64);
+        s.push_str("abc");
+        assert!(!s.shrink_if_wasteful(100.0));
+        assert!(s.shrink_if_wasteful(2.0));
+        assert!(!s.shrink_if_wasteful(f64::NAN));
+    }
+
+    #[test]
+    fn map_to_range_is_exact_for_small_ranges() {
+        assert_eq!(5i32.map_to_range(0, 10, 0, 100), 50);
+        assert_eq!(15u8.map_to_range(0, 10, 0, 100), 100);
+        assert_eq!(3i8.map_to_range(0, 10, 10, 0), 7);
+        assert_eq!(3u8.map_to_range(4, 4, 9, 20), 9);
+    }
+}
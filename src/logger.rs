@@ -1,13 +1,27 @@
+use std::fmt;
+use std::fmt::Display;
+use std::io::{self, Read, Write};
 #[cfg(not(feature = "async"))]
 use std::sync::RwLock;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "async")]
+use std::time::Duration;
+use std::time::SystemTime;
+
+use crate::datetime_utils::{DateTimeUtils, checked_from_unix, unix_parts};
 
 #[cfg(feature = "async")]
 use async_trait::async_trait;
 #[cfg(feature = "async")]
 use once_cell::sync::Lazy;
 #[cfg(feature = "async")]
-use tokio::sync::RwLock;
+use tokio::sync::{
+    RwLock,
+    mpsc::{self, UnboundedReceiver, UnboundedSender, error::SendError},
+    oneshot,
+};
+#[cfg(feature = "async")]
+use tokio::task::JoinHandle;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LogLevel {
@@ -21,10 +35,63 @@ struct Logger {
     message: String,
     level: LogLevel,
     time: SystemTime,
+    scope: Option<String>,
+}
+
+impl Logger {
+    fn new(level: LogLevel, scope: Option<&str>, message: &str) -> Self {
+        Self {
+            message: message.to_string(),
+            level,
+            time: SystemTime::now(),
+            scope: scope.map(str::to_string),
+        }
+    }
+
+    fn format(&self) -> String {
+        let scope = self
+            .scope
+            .as_ref()
+            .map(|scope| format!(" ({scope})"))
+            .unwrap_or_default();
+        format!(
+            "[{:?}]{} @ {} → {}",
+            self.level,
+            scope,
+            self.time.format_iso8601(),
+            self.message
+        )
+    }
 }
 
 pub struct Log;
 
+/// Callback run by [`Action::Callback`] with the record's level and message.
+pub type EscalationFn = Arc<dyn Fn(LogLevel, &str) + Send + Sync>;
+
+/// What [`Log::escalate_at`] does with a record at or above its level.
+#[derive(Clone)]
+pub enum Action {
+    /// Panics with the record's message.
+    Panic,
+    /// Prints the message to stderr and exits the process with the code.
+    Exit(i32),
+    /// Calls the function with the record's level and message.
+    Callback(EscalationFn),
+}
+
+impl fmt::Debug for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Panic => f.write_str("Panic"),
+            Self::Exit(code) => f.debug_tuple("Exit").field(code).finish(),
+            Self::Callback(_) => f.write_str("Callback(..)"),
+        }
+    }
+}
+
+static ESCALATION: Mutex<Option<(LogLevel, Action)>> = Mutex::new(None);
+
 #[cfg(feature = "async")]
 static LOGS: Lazy<RwLock<Vec<Logger>>> = Lazy::new(|| RwLock::new(Vec::new()));
 #[cfg(feature = "async")]
@@ -45,7 +112,6 @@ async fn get_level() -> LogLevel {
     *LOG_LEVEL.read().await
 }
 
-#[cfg(not(feature = "async"))]
 fn level_priority(level: LogLevel) -> u8 {
     match level {
         LogLevel::Error => 1,
@@ -55,13 +121,209 @@ fn level_priority(level: LogLevel) -> u8 {
     }
 }
 
+/// Runs the escalation action if `level` is at or above the configured level.
+fn escalate(level: LogLevel, message: &str) {
+    // Cloned out so the action never runs while the lock is held.
+    let Some((min, action)) = ESCALATION.lock().unwrap().clone() else {
+        return;
+    };
+    if level_priority(level) > level_priority(min) {
+        return;
+    }
+    match action {
+        Action::Panic => panic!("escalated {level:?} log: {message}"),
+        Action::Exit(code) => {
+            // Queued records, including this one, would be lost on exit.
+            #[cfg(feature = "async")]
+            flush_writer_blocking();
+            eprintln!("[FATAL]: {message}");
+            std::process::exit(code);
+        }
+        Action::Callback(f) => f(level, message),
+    }
+}
+
+const BINARY_MAGIC: &[u8; 4] = b"ULOG";
+const BINARY_VERSION: u8 = 1;
+
+/// Writes records as: magic, version, record count, then per record
+/// level, seconds, nanoseconds, optional scope and message (little-endian,
+/// strings length-prefixed with a `u32`).
+fn encode_logs<W: Write>(logs: &[Logger], mut writer: W) -> io::Result<usize> {
+    writer.write_all(BINARY_MAGIC)?;
+    writer.write_all(&[BINARY_VERSION])?;
+    writer.write_all(&(logs.len() as u64).to_le_bytes())?;
+    for log in logs {
+        let (secs, nanos) = unix_parts(log.time);
+        writer.write_all(&[level_priority(log.level)])?;
+        writer.write_all(&secs.to_le_bytes())?;
+        writer.write_all(&nanos.to_le_bytes())?;
+        match &log.scope {
+            Some(scope) => {
+                writer.write_all(&[1])?;
+                write_str(&mut writer, scope)?;
+            }
+            None => writer.write_all(&[0])?,
+        }
+        write_str(&mut writer, &log.message)?;
+    }
+    writer.flush()?;
+    Ok(logs.len())
+}
+
+fn decode_logs<R: Read>(mut reader: R) -> io::Result<Vec<Logger>> {
+    let mut magic = [0; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != BINARY_MAGIC {
+        return Err(invalid_data("not a utilz log dump"));
+    }
+    let [version] = read_array(&mut reader)?;
+    if version != BINARY_VERSION {
+        return Err(invalid_data(&format!(
+            "unsupported log dump version {version}"
+        )));
+    }
+
+    let count = u64::from_le_bytes(read_array(&mut reader)?);
+    let mut logs = Vec::new();
+    for _ in 0..count {
+        let [level] = read_array(&mut reader)?;
+        let level = match level {
+            1 => LogLevel::Error,
+            2 => LogLevel::Warn,
+            3 => LogLevel::Info,
+            4 => LogLevel::Debug,
+            _ => return Err(invalid_data(&format!("invalid log level {level}"))),
+        };
+        let secs = i64::from_le_bytes(read_array(&mut reader)?);
+        let nanos = u32::from_le_bytes(read_array(&mut reader)?);
+        let time = checked_from_unix(secs, nanos)
+            .ok_or_else(|| invalid_data("log timestamp out of range"))?;
+        let scope = match read_array(&mut reader)? {
+            [0] => None,
+            [1] => Some(read_str(&mut reader)?),
+            [flag] => return Err(invalid_data(&format!("invalid scope flag {flag}"))),
+        };
+        let message = read_str(&mut reader)?;
+        logs.push(Logger {
+            message,
+            level,
+            time,
+            scope,
+        });
+    }
+    Ok(logs)
+}
+
+fn write_str<W: Write>(writer: &mut W, s: &str) -> io::Result<()> {
+    let len = u32::try_from(s.len()).map_err(|_| invalid_data("string too long"))?;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(s.as_bytes())
+}
+
+fn read_str<R: Read>(reader: &mut R) -> io::Result<String> {
+    let len = u32::from_le_bytes(read_array(reader)?);
+    let mut bytes = Vec::new();
+    reader.take(u64::from(len)).read_to_end(&mut bytes)?;
+    if bytes.len() != len as usize {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    String::from_utf8(bytes).map_err(|_| invalid_data("log text is not UTF-8"))
+}
+
+fn read_array<R: Read, const N: usize>(reader: &mut R) -> io::Result<[u8; N]> {
+    let mut buf = [0; N];
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
 #[cfg(feature = "async")]
-async fn level_priority(level: LogLevel) -> u8 {
-    match level {
-        LogLevel::Error => 1,
-        LogLevel::Warn => 2,
-        LogLevel::Info => 3,
-        LogLevel::Debug => 4,
+enum WriterMsg {
+    Record(Logger),
+    Flush(oneshot::Sender<()>),
+}
+
+/// How long `logs_for_exit` waits for the writer or the store.
+#[cfg(feature = "async")]
+const EXIT_FLUSH_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// `tokio::time::interval` panics on a zero period.
+#[cfg(feature = "async")]
+const MIN_FLUSH_INTERVAL: Duration = Duration::from_millis(1);
+
+#[cfg(feature = "async")]
+static WRITER: Mutex<Option<UnboundedSender<WriterMsg>>> = Mutex::new(None);
+
+/// Hands the record to the background writer, or gives it back if none is running.
+#[cfg(feature = "async")]
+fn send_to_writer(log: Logger) -> Option<Logger> {
+    let writer = WRITER.lock().unwrap();
+    match writer.as_ref() {
+        Some(tx) => match tx.send(WriterMsg::Record(log)) {
+            Ok(()) => None,
+            Err(SendError(WriterMsg::Record(log))) => Some(log),
+            Err(_) => None,
+        },
+        None => Some(log),
+    }
+}
+
+#[cfg(feature = "async")]
+async fn write_batch<W: Write>(sink: &mut W, batch: &mut Vec<Logger>) {
+    if batch.is_empty() {
+        return;
+    }
+    for log in batch.iter() {
+        let _ = writeln!(sink, "{}", log.format());
+    }
+    let _ = sink.flush();
+    LOGS.write().await.append(batch);
+}
+
+#[cfg(feature = "async")]
+async fn run_writer<W: Write>(
+    mut sink: W,
+    flush_interval: Duration,
+    mut rx: UnboundedReceiver<WriterMsg>,
+) {
+    let mut batch = Vec::new();
+    let mut ticker = tokio::time::interval(flush_interval);
+    loop {
+        tokio::select! {
+            msg = rx.recv() => match msg {
+                Some(WriterMsg::Record(log)) => batch.push(log),
+                Some(WriterMsg::Flush(done)) => {
+                    write_batch(&mut sink, &mut batch).await;
+                    let _ = done.send(());
+                }
+                None => {
+                    write_batch(&mut sink, &mut batch).await;
+                    break;
+                }
+            },
+            _ = ticker.tick() => write_batch(&mut sink, &mut batch).await,
+        }
+    }
+}
+
+impl Log {
+    /// Turns every record at `level` or more severe into `action`, e.g. to
+    /// fail CI runs on the first error.
+    ///
+    /// Applies regardless of the logger's filter level, after the record is stored.
+    /// `Action::Exit` first flushes a running background writer, so the sink
+    /// keeps every record up to and including the escalating one.
+    pub fn escalate_at(level: LogLevel, action: Action) {
+        *ESCALATION.lock().unwrap() = Some((level, action));
+    }
+
+    /// Turns escalation off again.
+    pub fn clear_escalation() {
+        *ESCALATION.lock().unwrap() = None;
     }
 }
 
@@ -72,14 +334,56 @@ impl Log {
     }
 
     pub async fn log_with_level(level: LogLevel, message: &str) {
-        if level_priority(level).await <= level_priority(get_level().await).await {
-            let log = Logger {
-                message: message.to_string(),
-                level,
-                time: SystemTime::now(),
-            };
-
-            LOGS.write().await.push(log);
+        Self::record(level, None, message).await;
+    }
+
+    /// Logs `message` tagged with `scope`, e.g. the module or request it came
+    /// from. Scoped records print as `[Info] (scope) @ ... → message`.
+    pub async fn log_scoped(level: LogLevel, scope: &str, message: &str) {
+        Self::record(level, Some(scope), message).await;
+    }
+
+    async fn record(level: LogLevel, scope: Option<&str>, message: &str) {
+        if level_priority(level) <= level_priority(get_level().await) {
+            let log = Logger::new(level, scope, message);
+
+            if let Some(log) = send_to_writer(log) {
+                LOGS.write().await.push(log);
+            }
+        }
+        escalate(level, message);
+    }
+
+    /// Starts a background task that batches records and writes them to `sink`
+    /// every `flush_interval`.
+    ///
+    /// While the writer runs, logging only pushes into a channel and never waits
+    /// on the log store. Written records are still kept in memory, so
+    /// `get_logs()` sees them once their batch has been flushed.
+    ///
+    /// Must be called from within a Tokio runtime. Spawning a new writer
+    /// replaces the previous one, which drains its pending records and exits.
+    /// Intervals below one millisecond are raised to one millisecond.
+    pub fn spawn_writer<W: Write + Send + 'static>(
+        sink: W,
+        flush_interval: Duration,
+    ) -> JoinHandle<()> {
+        let flush_interval = flush_interval.max(MIN_FLUSH_INTERVAL);
+        let (tx, rx) = mpsc::unbounded_channel();
+        let handle = tokio::spawn(run_writer(sink, flush_interval, rx));
+        *WRITER.lock().unwrap() = Some(tx);
+        handle
+    }
+
+    /// Waits until every record sent to the background writer so far has been
+    /// written to its sink. Returns immediately if no writer is running.
+    pub async fn flush() {
+        let Some(tx) = WRITER.lock().unwrap().clone() else {
+            return;
+        };
+        let (done_tx, done_rx) = oneshot::channel();
+        if tx.send(WriterMsg::Flush(done_tx)).is_ok() {
+            let _ = done_rx.await;
         }
     }
 
@@ -104,22 +408,7 @@ impl Log {
     }
 
     pub async fn get_logs() -> Vec<String> {
-        LOGS.read()
-            .await
-            .iter()
-            .map(|log| {
-                let since_unix = log
-                    .time
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap_or(Duration::from_secs(0));
-                format!(
-                    "[{:?}] @ {}s → {}",
-                    log.level,
-                    since_unix.as_secs(),
-                    log.message
-                )
-            })
-            .collect()
+        LOGS.read().await.iter().map(Logger::format).collect()
     }
 
     pub async fn print_logs() {
@@ -131,6 +420,23 @@ impl Log {
     pub async fn clear() {
         LOGS.write().await.clear();
     }
+
+    /// Writes all stored records to `writer` in a compact binary format.
+    ///
+    /// Returns the number of records written.
+    pub async fn export_binary<W: Write>(writer: W) -> io::Result<usize> {
+        encode_logs(&LOGS.read().await, writer)
+    }
+
+    /// Reads records written by `export_binary` and appends them to the store.
+    ///
+    /// Returns the number of records imported.
+    pub async fn import_binary<R: Read>(reader: R) -> io::Result<usize> {
+        let mut logs = decode_logs(reader)?;
+        let count = logs.len();
+        LOGS.write().await.append(&mut logs);
+        Ok(count)
+    }
 }
 
 #[cfg(not(feature = "async"))]
@@ -140,15 +446,22 @@ impl Log {
     }
 
     pub fn log_with_level(level: LogLevel, message: &str) {
+        Self::record(level, None, message);
+    }
+
+    /// Logs `message` tagged with `scope`, e.g. the module or request it came
+    /// from. Scoped records print as `[Info] (scope) @ ... → message`.
+    pub fn log_scoped(level: LogLevel, scope: &str, message: &str) {
+        Self::record(level, Some(scope), message);
+    }
+
+    fn record(level: LogLevel, scope: Option<&str>, message: &str) {
         if level_priority(level) <= level_priority(get_level()) {
-            let log = Logger {
-                message: message.to_string(),
-                level,
-                time: SystemTime::now(),
-            };
+            let log = Logger::new(level, scope, message);
 
             LOGS.write().unwrap().push(log);
         }
+        escalate(level, message);
     }
 
     pub fn log(message: &str) {
@@ -172,22 +485,7 @@ impl Log {
     }
 
     pub fn get_logs() -> Vec<String> {
-        LOGS.read()
-            .unwrap()
-            .iter()
-            .map(|log| {
-                let since_unix = log
-                    .time
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap_or(Duration::from_secs(0));
-                format!(
-                    "[{:?}] @ {}s → {}",
-                    log.level,
-                    since_unix.as_secs(),
-                    log.message
-                )
-            })
-            .collect()
+        LOGS.read().unwrap().iter().map(Logger::format).collect()
     }
 
     pub fn print_logs() {
@@ -199,70 +497,347 @@ impl Log {
     pub fn clear() {
         LOGS.write().unwrap().clear();
     }
+
+    /// Writes all stored records to `writer` in a compact binary format.
+    ///
+    /// Returns the number of records written.
+    pub fn export_binary<W: Write>(writer: W) -> io::Result<usize> {
+        encode_logs(&LOGS.read().unwrap(), writer)
+    }
+
+    /// Reads records written by `export_binary` and appends them to the store.
+    ///
+    /// Returns the number of records imported.
+    pub fn import_binary<R: Read>(reader: R) -> io::Result<usize> {
+        let mut logs = decode_logs(reader)?;
+        let count = logs.len();
+        LOGS.write().unwrap().append(&mut logs);
+        Ok(count)
+    }
+}
+
+/// Records a log entry from synchronous code.
+///
+/// Under the `async` feature this never awaits: records go to the background
+/// writer if one runs, otherwise straight into the store when it's free, or
+/// via a spawned task when it's contended.
+#[cfg(feature = "async")]
+pub(crate) fn log_sync(level: LogLevel, message: &str) {
+    store_sync(level, message);
+    escalate(level, message);
+}
+
+#[cfg(feature = "async")]
+fn store_sync(level: LogLevel, message: &str) {
+    if let Ok(current) = LOG_LEVEL.try_read()
+        && level_priority(level) > level_priority(*current)
+    {
+        return;
+    }
+    let log = Logger::new(level, None, message);
+    let Some(log) = send_to_writer(log) else {
+        return;
+    };
+    match LOGS.try_write() {
+        Ok(mut logs) => logs.push(log),
+        Err(_) => match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                handle.spawn(async move { LOGS.write().await.push(log) });
+            }
+            Err(_) => LOGS.blocking_write().push(log),
+        },
+    }
+}
+
+/// Records a log entry from synchronous code.
+#[cfg(not(feature = "async"))]
+pub(crate) fn log_sync(level: LogLevel, message: &str) {
+    Log::log_with_level(level, message);
+}
+
+/// Flushes the background writer from synchronous code, waiting at most
+/// `EXIT_FLUSH_TIMEOUT`. Returns `false` if no writer is running.
+///
+/// Can't wait on a current-thread runtime's own thread, since the writer
+/// would never get to run; call `Log::flush().await` there instead.
+#[cfg(feature = "async")]
+fn flush_writer_blocking() -> bool {
+    let Some(tx) = WRITER.lock().unwrap().clone() else {
+        return false;
+    };
+    if let Ok(handle) = tokio::runtime::Handle::try_current()
+        && handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::CurrentThread
+    {
+        return true;
+    }
+    // Sent from a helper thread so the writer's wake-up goes to the shared
+    // queue, not to a runtime worker that is blocked here waiting.
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            let (done_tx, mut done_rx) = oneshot::channel();
+            if tx.send(WriterMsg::Flush(done_tx)).is_err() {
+                return;
+            }
+            let deadline = std::time::Instant::now() + EXIT_FLUSH_TIMEOUT;
+            while matches!(done_rx.try_recv(), Err(oneshot::error::TryRecvError::Empty))
+                && std::time::Instant::now() < deadline
+            {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+        });
+    });
+    true
+}
+
+/// Returns the records an exiting program should still print, without awaiting.
+///
+/// If a background writer is running, its pending records are flushed to its
+/// sink instead (see `flush_writer_blocking`) and nothing is returned, since
+/// the sink already has every record. Without a writer, the whole store is
+/// returned.
+#[cfg(feature = "async")]
+pub(crate) fn logs_for_exit() -> Vec<String> {
+    if flush_writer_blocking() {
+        return Vec::new();
+    }
+
+    let in_runtime = tokio::runtime::Handle::try_current().is_ok();
+    if !in_runtime {
+        return LOGS.blocking_read().iter().map(Logger::format).collect();
+    }
+    // Writers never hold the lock across an await, so contention is brief.
+    let deadline = std::time::Instant::now() + EXIT_FLUSH_TIMEOUT;
+    loop {
+        if let Ok(logs) = LOGS.try_read() {
+            return logs.iter().map(Logger::format).collect();
+        }
+        if std::time::Instant::now() >= deadline {
+            return Vec::new();
+        }
+        std::thread::yield_now();
+    }
 }
 
+/// Returns the records an exiting program should still print.
+#[cfg(not(feature = "async"))]
+pub(crate) fn logs_for_exit() -> Vec<String> {
+    Log::get_logs()
+}
+
+/// Logs any displayable value through [`Log`].
+///
+/// Implemented for every `T: Display`, so strings, numbers, `std::io::Error`,
+/// `Cow<str>` and friends can be logged without a `format!` call.
 #[cfg(feature = "async")]
 #[async_trait(?Send)]
 pub trait Loggable: Sized + Send + 'static {
     async fn log(&self);
     async fn log_info(&self);
+    async fn log_warn(&self);
     async fn log_error(&self);
     async fn log_debug(&self);
 }
 
+/// Logs any displayable value through [`Log`].
+///
+/// Implemented for every `T: Display`, so strings, numbers, `std::io::Error`,
+/// `Cow<str>` and friends can be logged without a `format!` call.
+///
+/// Note that floats already have an inherent `log(base)` method, so use
+/// `Loggable::log(x)` or one of the leveled methods for them.
 #[cfg(not(feature = "async"))]
 pub trait Loggable {
     fn log(self);
     fn log_info(self);
+    fn log_warn(self);
     fn log_error(self);
     fn log_debug(self);
 }
 
 #[cfg(feature = "async")]
 #[async_trait(?Send)]
-impl Loggable for String {
+impl<T: Display + Send + 'static> Loggable for T {
     async fn log(&self) {
-        Log::log(self).await
+        Log::log(&self.to_string()).await
     }
     async fn log_info(&self) {
-        Log::log_info(self).await
+        Log::log_info(&self.to_string()).await
+    }
+    async fn log_warn(&self) {
+        Log::log_warn(&self.to_string()).await
     }
     async fn log_error(&self) {
-        Log::log_error(self).await
+        Log::log_error(&self.to_string()).await
     }
     async fn log_debug(&self) {
-        Log::log_debug(self).await
+        Log::log_debug(&self.to_string()).await
     }
 }
 
 #[cfg(not(feature = "async"))]
-impl Loggable for &str {
+impl<T: Display> Loggable for T {
     fn log(self) {
-        Log::log(self);
+        Log::log(&self.to_string());
     }
     fn log_info(self) {
-        Log::log_info(self);
+        Log::log_info(&self.to_string());
+    }
+    fn log_warn(self) {
+        Log::log_warn(&self.to_string());
     }
     fn log_error(self) {
-        Log::log_error(self);
+        Log::log_error(&self.to_string());
     }
     fn log_debug(self) {
-        Log::log_debug(self);
+        Log::log_debug(&self.to_string());
     }
 }
 
-#[cfg(not(feature = "async"))]
-impl Loggable for String {
-    fn log(self) {
-        self.as_str().log()
+/// Serializes tests that log or change global logger state, so one test's
+/// filter, writer or escalation doesn't leak into records of another.
+#[cfg(test)]
+pub(crate) fn lock_logger_for_test() -> std::sync::MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    LOCK.lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dump(secs: i64, nanos: u32, scope_flag: u8) -> Vec<u8> {
+        let mut bytes = BINARY_MAGIC.to_vec();
+        bytes.push(BINARY_VERSION);
+        bytes.extend_from_slice(&1u64.to_le_bytes());
+        bytes.push(level_priority(LogLevel::Info));
+        bytes.extend_from_slice(&secs.to_le_bytes());
+        bytes.extend_from_slice(&nanos.to_le_bytes());
+        bytes.push(scope_flag);
+        if scope_flag != 0 {
+            write_str(&mut bytes, "scope").unwrap();
+        }
+        write_str(&mut bytes, "message").unwrap();
+        bytes
     }
-    fn log_info(self) {
-        self.as_str().log_info()
+
+    #[test]
+    fn decode_round_trips_a_valid_record() {
+        let logs = decode_logs(&dump(1_700_000_000, 5, 1)[..]).unwrap();
+        assert_eq!(logs[0].scope.as_deref(), Some("scope"));
+        assert_eq!(logs[0].message, "message");
     }
-    fn log_error(self) {
-        self.as_str().log_error()
+
+    #[test]
+    fn decode_rejects_out_of_range_timestamps() {
+        for (secs, nanos) in [(i64::MAX, u32::MAX), (0, 1_000_000_000)] {
+            let err = decode_logs(&dump(secs, nanos, 0)[..]).err().unwrap();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        }
     }
-    fn log_debug(self) {
-        self.as_str().log_debug()
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn zero_flush_interval_keeps_the_writer_alive() {
+        let _logger = lock_logger_for_test();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let sink = Arc::new(Mutex::new(Vec::new()));
+            struct Shared(Arc<Mutex<Vec<u8>>>);
+            impl Write for Shared {
+                fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                    self.0.lock().unwrap().write(buf)
+                }
+                fn flush(&mut self) -> io::Result<()> {
+                    Ok(())
+                }
+            }
+            let handle = Log::spawn_writer(Shared(sink.clone()), Duration::ZERO);
+            Log::log_error("written through a zero interval").await;
+            Log::flush().await;
+            assert!(!handle.is_finished());
+            let written = String::from_utf8(sink.lock().unwrap().clone()).unwrap();
+            assert!(written.contains("written through a zero interval"));
+            WRITER.lock().unwrap().take();
+        });
+    }
+
+    #[cfg(not(feature = "async"))]
+    #[test]
+    fn scoped_records_show_their_scope() {
+        let _logger = lock_logger_for_test();
+        Log::log_scoped(LogLevel::Error, "db", "scoped record");
+        let logs = Log::get_logs();
+        let line = logs.iter().find(|l| l.ends_with("scoped record")).unwrap();
+        assert!(line.starts_with("[Error] (db) @ "), "{line}");
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn scoped_records_show_their_scope() {
+        let _logger = lock_logger_for_test();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            Log::log_scoped(LogLevel::Error, "db", "scoped record").await;
+            let logs = Log::get_logs().await;
+            let line = logs.iter().find(|l| l.ends_with("scoped record")).unwrap();
+            assert!(line.starts_with("[Error] (db) @ "), "{line}");
+        });
+    }
+
+    /// Runs itself in a child process, since the escalation exits the process.
+    #[cfg(feature = "async")]
+    #[test]
+    fn exit_escalation_flushes_the_background_writer() {
+        const SINK_VAR: &str = "UTILZ_TEST_ESCALATION_SINK";
+        if let Ok(path) = std::env::var(SINK_VAR) {
+            let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+            std::thread::spawn(move || {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_time()
+                    .build()
+                    .unwrap();
+                runtime.block_on(async move {
+                    let sink = std::fs::File::create(path).unwrap();
+                    Log::spawn_writer(sink, Duration::from_millis(200));
+                    ready_tx.send(()).unwrap();
+                    std::future::pending::<()>().await;
+                });
+            });
+            ready_rx.recv().unwrap();
+            Log::escalate_at(LogLevel::Error, Action::Exit(3));
+            log_sync(LogLevel::Info, "before");
+            log_sync(LogLevel::Error, "boom");
+            unreachable!("escalation should have exited");
+        }
+
+        let path =
+            std::env::temp_dir().join(format!("utilz-escalation-{}.log", std::process::id()));
+        let status = std::process::Command::new(std::env::current_exe().unwrap())
+            .args([
+                "--exact",
+                "logger::tests::exit_escalation_flushes_the_background_writer",
+            ])
+            .env(SINK_VAR, &path)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .unwrap();
+        let written = std::fs::read_to_string(&path).unwrap_or_default();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(status.code(), Some(3));
+        assert!(written.contains("before"), "sink: {written:?}");
+        assert!(written.contains("boom"), "sink: {written:?}");
+    }
+
+    #[test]
+    fn decode_rejects_unknown_scope_flags() {
+        let err = decode_logs(&dump(0, 0, 2)[..]).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
     }
 }
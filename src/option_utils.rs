@@ -13,6 +13,14 @@ pub trait OptionUtils<T> {
     ///
     /// Returns nothing back.
     fn if_none<F: FnOnce()>(self, f: F);
+
+    /// Returns `self` if `Some`, otherwise the first `Some` produced by `fallbacks`.
+    ///
+    /// Fallbacks are called lazily, in order, and stop at the first hit.
+    fn or_else_chain<I, F>(self, fallbacks: I) -> Option<T>
+    where
+        I: IntoIterator<Item = F>,
+        F: FnOnce() -> Option<T>;
 }
 
 impl<T> OptionUtils<T> for Option<T> {
@@ -32,4 +40,23 @@ impl<T> OptionUtils<T> for Option<T> {
             f()
         }
     }
+
+    fn or_else_chain<I, F>(self, fallbacks: I) -> Option<T>
+    where
+        I: IntoIterator<Item = F>,
+        F: FnOnce() -> Option<T>,
+    {
+        self.or_else(|| first_some(fallbacks))
+    }
+}
+
+/// Calls each source in order and returns the first `Some`.
+///
+/// Useful for layered config lookups (CLI > env > file > default).
+pub fn first_some<T, I, F>(sources: I) -> Option<T>
+where
+    I: IntoIterator<Item = F>,
+    F: FnOnce() -> Option<T>,
+{
+    sources.into_iter().find_map(|f| f())
 }
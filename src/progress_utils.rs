@@ -0,0 +1,201 @@
+use std::{
+    io::{IsTerminal, Write},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    logger::{LogLevel, log_sync},
+    term_utils::width::WidthUtils,
+};
+
+const BAR_WIDTH: usize = 30;
+const REDRAW_INTERVAL: Duration = Duration::from_millis(50);
+const LOG_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Progress handle for long-running work.
+///
+/// Draws a bar on stderr when it's a terminal, and otherwise records a `Log`
+/// entry about once a second. Finishes automatically when dropped.
+#[derive(Debug)]
+pub struct Progress {
+    label: String,
+    total: Option<u64>,
+    current: u64,
+    message: String,
+    tty: bool,
+    started: Instant,
+    last_report: Option<Instant>,
+    last_width: usize,
+    finished: bool,
+}
+
+impl Progress {
+    /// Starts tracking progress towards `total` items, if known.
+    pub fn new(label: impl Into<String>, total: Option<u64>) -> Self {
+        Self {
+            label: label.into(),
+            total,
+            current: 0,
+            message: String::new(),
+            tty: std::io::stderr().is_terminal(),
+            started: Instant::now(),
+            last_report: None,
+            last_width: 0,
+            finished: false,
+        }
+    }
+
+    /// Advances by one item.
+    pub fn inc(&mut self) {
+        self.inc_by(1);
+    }
+
+    /// Advances by `n` items.
+    pub fn inc_by(&mut self, n: u64) {
+        self.current = self.current.saturating_add(n);
+        self.report(false);
+    }
+
+    /// Sets the text shown next to the counter.
+    pub fn set_message(&mut self, message: impl Into<String>) {
+        self.message = message.into();
+        self.report(false);
+    }
+
+    /// Returns the number of items done so far.
+    pub fn position(&self) -> u64 {
+        self.current
+    }
+
+    /// Reports the final state. Later calls do nothing.
+    pub fn finish(&mut self) {
+        if self.finished {
+            return;
+        }
+        self.finished = true;
+        self.report(true);
+    }
+
+    fn report(&mut self, force: bool) {
+        let interval = if self.tty {
+            REDRAW_INTERVAL
+        } else {
+            LOG_INTERVAL
+        };
+        let due = self.last_report.is_none_or(|at| at.elapsed() >= interval);
+        if !force && (self.finished || !due) {
+            return;
+        }
+        self.last_report = Some(Instant::now());
+
+        if self.tty {
+            self.draw_bar();
+        } else {
+            log_sync(LogLevel::Info, &self.status_line());
+        }
+    }
+
+    fn draw_bar(&mut self) {
+        let bar = match self.total {
+            Some(total) if total > 0 => {
+                let filled = (self.current.min(total) * BAR_WIDTH as u64 / total) as usize;
+                format!(
+                    "[{}{}] ",
+                    "#".repeat(filled),
+                    ".".repeat(BAR_WIDTH - filled)
+                )
+            }
+            _ => String::new(),
+        };
+        let mut line = format!("{} {}{}", self.label, bar, self.counter());
+        if !self.message.is_empty() {
+            line.push_str(&format!(" {}", self.message));
+        }
+        let width = line.display_width();
+        // Pad over the previous line in case it was longer.
+        let line = line.pad_display(self.last_width);
+        self.last_width = width;
+
+        let mut stderr = std::io::stderr().lock();
+        let _ = write!(stderr, "\r{line}");
+        if self.finished {
+            let _ = writeln!(stderr);
+        }
+        let _ = stderr.flush();
+    }
+
+    fn status_line(&self) -> String {
+        let mut line = format!("{}: {}", self.label, self.counter());
+        if self.finished {
+            line.push_str(&format!(" done in {:.1?}", self.started.elapsed()));
+        }
+        if !self.message.is_empty() {
+            line.push_str(&format!(" {}", self.message));
+        }
+        line
+    }
+
+    fn counter(&self) -> String {
+        match self.total {
+            Some(total) if total > 0 => format!(
+                "{}/{} ({}%)",
+                self.current,
+                total,
+                self.current.min(total) * 100 / total
+            ),
+            Some(total) => format!("{}/{}", self.current, total),
+            None => self.current.to_string(),
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+/// Iterator adapter returned by [`ProgressIteratorUtils::with_progress`].
+#[derive(Debug)]
+pub struct WithProgress<I> {
+    iter: I,
+    progress: Progress,
+}
+
+impl<I: Iterator> Iterator for WithProgress<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let item = self.iter.next();
+        match item {
+            Some(_) => self.progress.inc(),
+            None => self.progress.finish(),
+        }
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Progress reporting for any iterator.
+pub trait ProgressIteratorUtils: Iterator + Sized {
+    /// Reports progress under `label` as items are consumed.
+    ///
+    /// The total is taken from the iterator's size hint when it's exact.
+    fn with_progress(self, label: &str) -> WithProgress<Self>;
+}
+
+impl<I: Iterator> ProgressIteratorUtils for I {
+    fn with_progress(self, label: &str) -> WithProgress<Self> {
+        let total = match self.size_hint() {
+            (lower, Some(upper)) if lower == upper => Some(lower as u64),
+            _ => None,
+        };
+        WithProgress {
+            iter: self,
+            progress: Progress::new(label, total),
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use std::{
+    cell::RefCell,
+    collections::hash_map::RandomState,
+    fmt,
+    hash::{BuildHasher, Hasher},
+    time::SystemTime,
+};
+
+/// Small, fast pseudo-random generator (SplitMix64).
+///
+/// Good enough for games and simulations; **not** suitable for cryptography.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Creates a generator seeded from the process' hash seed and the clock.
+    pub fn new() -> Self {
+        let mut hasher = RandomState::new().build_hasher();
+        if let Ok(d) = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+            hasher.write_u128(d.as_nanos());
+        }
+        Self::with_seed(hasher.finish())
+    }
+
+    /// Creates a generator that always yields the same sequence for `seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next random `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random `f64` in `[0.0, 1.0)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Returns a random integer in `low..=high`.
+    ///
+    /// Panics if `low > high`.
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        assert!(low <= high, "empty range {low}..={high}");
+        let span = high.abs_diff(low).wrapping_add(1);
+        if span == 0 {
+            return self.next_u64() as i64;
+        }
+        // Rejection sampling keeps the result unbiased.
+        let zone = u64::MAX - u64::MAX % span;
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return low.wrapping_add((x % span) as i64);
+            }
+        }
+    }
+
+    /// Returns an approximately normal sample using the central limit theorem.
+    pub fn normal_approx(&mut self, mean: f64, sd: f64) -> f64 {
+        let sum: f64 = (0..12).map(|_| self.next_f64()).sum();
+        mean + (sum - 6.0) * sd
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+thread_local! {
+    static THREAD_RNG: RefCell<Rng> = RefCell::new(Rng::new());
+}
+
+/// Runs `f` with this thread's shared generator.
+pub fn with_rng<R>(f: impl FnOnce(&mut Rng) -> R) -> R {
+    THREAD_RNG.with(|rng| f(&mut rng.borrow_mut()))
+}
+
+/// Returns an approximately normal sample with the given mean and standard deviation.
+pub fn normal_approx(mean: f64, sd: f64) -> f64 {
+    with_rng(|rng| rng.normal_approx(mean, sd))
+}
+
+/// Error returned when dice notation can't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiceError {
+    notation: String,
+}
+
+impl fmt::Display for DiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid dice notation `{}`", self.notation)
+    }
+}
+
+impl std::error::Error for DiceError {}
+
+/// Most dice a single term may roll, e.g. the `1000` in `"1000d6"`.
+const MAX_DICE: u32 = 1_000;
+/// Most sides a single die may have.
+const MAX_SIDES: i64 = 1_000_000;
+
+/// Rolls dice notation like `"3d6+2"`, `"d20"` or `"2d8 + 1d4 - 1"`.
+///
+/// A term may roll at most 1000 dice with at most 1,000,000 sides each;
+/// larger terms are rejected as invalid.
+pub fn roll(notation: &str) -> Result<i64, DiceError> {
+    with_rng(|rng| roll_with(rng, notation))
+}
+
+/// Same as [`roll`], using the given generator.
+pub fn roll_with(rng: &mut Rng, notation: &str) -> Result<i64, DiceError> {
+    let invalid = || DiceError {
+        notation: notation.to_string(),
+    };
+    let compact: String = notation.chars().filter(|c| !c.is_whitespace()).collect();
+    if compact.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut total: i64 = 0;
+    let mut rest = compact.as_str();
+    let mut sign = 1;
+    if let Some(stripped) = rest.strip_prefix('-') {
+        sign = -1;
+        rest = stripped;
+    }
+    loop {
+        let end = rest.find(['+', '-']).unwrap_or(rest.len());
+        let term = &rest[..end];
+        let value = match term.split_once(['d', 'D']) {
+            Some((count, sides)) => {
+                let count: u32 = if count.is_empty() {
+                    1
+                } else {
+                    count.parse().map_err(|_| invalid())?
+                };
+                let sides: i64 = sides.parse().map_err(|_| invalid())?;
+                if count > MAX_DICE || !(1..=MAX_SIDES).contains(&sides) {
+                    return Err(invalid());
+                }
+                (0..count).fold(0i64, |sum, _| sum.saturating_add(rng.range(1, sides)))
+            }
+            None => term.parse::<i64>().map_err(|_| invalid())?,
+        };
+        total = total.saturating_add(value.saturating_mul(sign));
+
+        match rest[end..].chars().next() {
+            Some(op) => {
+                sign = if op == '+' { 1 } else { -1 };
+                rest = &rest[end + 1..];
+            }
+            None => return Ok(total),
+        }
+    }
+}
+
+/// Weighted random selection from slices.
+pub trait WeightedUtils<T> {
+    /// Picks an item with probability proportional to `weight(item)`.
+    ///
+    /// Negative or non-finite weights count as zero. Returns `None` if the
+    /// slice is empty or every weight is zero.
+    fn pick_weighted<F: Fn(&T) -> f64>(&self, weight: F) -> Option<&T>;
+
+    /// Same as [`pick_weighted`](Self::pick_weighted), using the given generator.
+    fn pick_weighted_with<F: Fn(&T) -> f64>(&self, rng: &mut Rng, weight: F) -> Option<&T>;
+}
+
+impl<T> WeightedUtils<T> for [T] {
+    fn pick_weighted<F: Fn(&T) -> f64>(&self, weight: F) -> Option<&T> {
+        with_rng(|rng| self.pick_weighted_with(rng, weight))
+    }
+
+    fn pick_weighted_with<F: Fn(&T) -> f64>(&self, rng: &mut Rng, weight: F) -> Option<&T> {
+        let weight = |item: &T| {
+            let w = weight(item);
+            if w.is_finite() && w > 0.0 { w } else { 0.0 }
+        };
+        let total: f64 = self.iter().map(weight).sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut target = rng.next_f64() * total;
+        let mut last = None;
+        for item in self {
+            let w = weight(item);
+            if w == 0.0 {
+                continue;
+            }
+            if target < w {
+                return Some(item);
+            }
+            target -= w;
+            last = Some(item);
+        }
+        // Float rounding can leave a sliver past the final item.
+        last
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oversized_dice_terms_are_rejected() {
+        let mut rng = Rng::with_seed(1);
+        for notation in ["1000d9223372036854775807", "4294967295d6", "1001d6", "2d0"] {
+            assert!(roll_with(&mut rng, notation).is_err(), "{notation}");
+        }
+    }
+
+    #[test]
+    fn huge_flat_modifiers_saturate() {
+        let mut rng = Rng::with_seed(1);
+        assert_eq!(roll_with(&mut rng, "9223372036854775807+1d6"), Ok(i64::MAX));
+        assert_eq!(
+            roll_with(&mut rng, "-9223372036854775807-1d6"),
+            Ok(i64::MIN)
+        );
+    }
+
+    #[test]
+    fn largest_allowed_term_stays_in_range() {
+        let mut rng = Rng::with_seed(7);
+        let total = roll_with(&mut rng, "1000d1000000").unwrap();
+        assert!((1_000..=1_000_000_000).contains(&total));
+    }
+}
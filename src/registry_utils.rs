@@ -0,0 +1,130 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt,
+    sync::{Arc, RwLock},
+};
+
+type Service = Arc<dyn Any + Send + Sync>;
+
+struct Inner {
+    services: RwLock<HashMap<TypeId, Service>>,
+    parent: Option<TypeRegistry>,
+}
+
+/// Thread-safe service locator keyed by type.
+///
+/// Cloning is cheap and every clone shares the same services. Child registries
+/// see their parent's services but can override them without affecting it.
+#[derive(Clone)]
+pub struct TypeRegistry {
+    inner: Arc<Inner>,
+}
+
+impl TypeRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::with_parent(None)
+    }
+
+    fn with_parent(parent: Option<TypeRegistry>) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                services: RwLock::new(HashMap::new()),
+                parent,
+            }),
+        }
+    }
+
+    /// Registers `instance` as the `T` service, replacing any previous one in this registry.
+    pub fn provide<T: Any + Send + Sync>(&self, instance: T) -> &Self {
+        self.provide_arc(Arc::new(instance))
+    }
+
+    /// Same as [`provide`](Self::provide) for an instance that is already shared.
+    pub fn provide_arc<T: Any + Send + Sync>(&self, instance: Arc<T>) -> &Self {
+        self.inner
+            .services
+            .write()
+            .unwrap()
+            .insert(TypeId::of::<T>(), instance);
+        self
+    }
+
+    /// Returns the `T` service from this registry or, failing that, its parents.
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        let local = self
+            .inner
+            .services
+            .read()
+            .unwrap()
+            .get(&TypeId::of::<T>())
+            .cloned();
+        match local {
+            Some(service) => service.downcast().ok(),
+            None => self.inner.parent.as_ref()?.get(),
+        }
+    }
+
+    /// Returns `true` if a `T` service is visible from this registry.
+    pub fn contains<T: Any + Send + Sync>(&self) -> bool {
+        self.get::<T>().is_some()
+    }
+
+    /// Returns the `T` service, creating and registering it with `init` if missing.
+    ///
+    /// `init` runs without holding the lock, so it may use the registry itself.
+    /// If two threads race, the first value stored wins and both get it.
+    pub fn get_or_init<T, F>(&self, init: F) -> Arc<T>
+    where
+        T: Any + Send + Sync,
+        F: FnOnce() -> T,
+    {
+        if let Some(service) = self.get() {
+            return service;
+        }
+        let created: Service = Arc::new(init());
+        let stored = self
+            .inner
+            .services
+            .write()
+            .unwrap()
+            .entry(TypeId::of::<T>())
+            .or_insert(created)
+            .clone();
+        stored
+            .downcast()
+            .unwrap_or_else(|_| unreachable!("service stored under the wrong type"))
+    }
+
+    /// Removes the `T` service from this registry, returning it. Parents are untouched.
+    pub fn remove<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        self.inner
+            .services
+            .write()
+            .unwrap()
+            .remove(&TypeId::of::<T>())?
+            .downcast()
+            .ok()
+    }
+
+    /// Creates a scoped registry that falls back to `self` for missing services.
+    pub fn child(&self) -> Self {
+        Self::with_parent(Some(self.clone()))
+    }
+}
+
+impl Default for TypeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for TypeRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypeRegistry")
+            .field("services", &self.inner.services.read().unwrap().len())
+            .field("has_parent", &self.inner.parent.is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,318 @@
+use std::{fmt, str::FromStr, time::SystemTime};
+
+use crate::datetime_utils::{
+    SECS_PER_DAY, civil_from_days, days_from_civil, from_unix_secs, unix_parts,
+};
+
+/// How far ahead `next_after` searches before giving up (covers leap days).
+const SEARCH_LIMIT_SECS: i64 = 5 * 366 * SECS_PER_DAY;
+
+/// Error returned when a cron expression can't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleError {
+    /// The expression didn't have exactly five fields.
+    FieldCount(usize),
+    /// A field contained an invalid value, range or step.
+    InvalidField { field: &'static str, value: String },
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FieldCount(n) => write!(f, "expected 5 cron fields, found {n}"),
+            Self::InvalidField { field, value } => write!(f, "invalid {field} field `{value}`"),
+        }
+    }
+}
+
+impl std::error::Error for ScheduleError {}
+
+/// A cron-like schedule evaluated in UTC.
+///
+/// Parsed from the classic five fields `minute hour day-of-month month day-of-week`,
+/// each accepting `*`, numbers, ranges (`1-5`), lists (`1,15`) and steps (`*/5`, `0-30/10`).
+/// Day-of-week runs from `0` (Sunday) to `6`, with `7` also meaning Sunday. As in cron,
+/// when both day fields are restricted a day matches if either one does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Schedule {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+impl Schedule {
+    const ANY: Schedule = Schedule {
+        minutes: mask(0, 59),
+        hours: mask(0, 23),
+        days: mask(1, 31),
+        months: mask(1, 12),
+        weekdays: mask(0, 6),
+        days_restricted: false,
+        weekdays_restricted: false,
+    };
+
+    /// Parses a five-field cron expression such as `"*/5 * * * *"`.
+    pub fn parse(expr: &str) -> Result<Self, ScheduleError> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(ScheduleError::FieldCount(fields.len()));
+        };
+
+        let mut weekdays = parse_field(weekday, "day-of-week", 0, 7)?;
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays | 1) & !(1 << 7);
+        }
+
+        Ok(Self {
+            minutes: parse_field(minute, "minute", 0, 59)?,
+            hours: parse_field(hour, "hour", 0, 23)?,
+            days: parse_field(day, "day-of-month", 1, 31)?,
+            months: parse_field(month, "month", 1, 12)?,
+            weekdays,
+            days_restricted: day != "*",
+            weekdays_restricted: weekday != "*",
+        })
+    }
+
+    /// Returns the first matching time strictly after `time`, truncated to the minute.
+    ///
+    /// Returns `None` if nothing matches within the next five years
+    /// (e.g. `"0 0 30 2 *"`).
+    pub fn next_after(&self, time: SystemTime) -> Option<SystemTime> {
+        let start = unix_parts(time).0.div_euclid(60) * 60 + 60;
+        let mut secs = start;
+
+        while secs - start <= SEARCH_LIMIT_SECS {
+            let days = secs.div_euclid(SECS_PER_DAY);
+            let (year, month, _) = civil_from_days(days);
+
+            if !has_bit(self.months, month) {
+                let (year, month) = if month == 12 {
+                    (year + 1, 1)
+                } else {
+                    (year, month + 1)
+                };
+                secs = days_from_civil(year, month, 1) * SECS_PER_DAY;
+                continue;
+            }
+            if !self.matches_day(days) {
+                secs = (days + 1) * SECS_PER_DAY;
+                continue;
+            }
+
+            let secs_of_day = secs.rem_euclid(SECS_PER_DAY);
+            let hour = (secs_of_day / 3600) as u32;
+            let minute = (secs_of_day % 3600 / 60) as u32;
+            if !has_bit(self.hours, hour) {
+                secs = days * SECS_PER_DAY + (i64::from(hour) + 1) * 3600;
+                continue;
+            }
+            if !has_bit(self.minutes, minute) {
+                secs += 60;
+                continue;
+            }
+            return Some(from_unix_secs(secs));
+        }
+        None
+    }
+
+    /// Returns an iterator over upcoming run times, starting from now.
+    pub fn iter_upcoming(&self) -> Upcoming<'_> {
+        self.iter_after(SystemTime::now())
+    }
+
+    /// Returns an iterator over run times strictly after `time`.
+    pub fn iter_after(&self, time: SystemTime) -> Upcoming<'_> {
+        Upcoming {
+            schedule: self,
+            cursor: Some(time),
+        }
+    }
+
+    fn matches_day(&self, days: i64) -> bool {
+        let (_, _, day) = civil_from_days(days);
+        let weekday = (days + 4).rem_euclid(7) as u32;
+        let day_ok = has_bit(self.days, day);
+        let weekday_ok = has_bit(self.weekdays, weekday);
+
+        if self.days_restricted && self.weekdays_restricted {
+            day_ok || weekday_ok
+        } else {
+            day_ok && weekday_ok
+        }
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = ScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Iterator over upcoming run times of a [`Schedule`].
+#[derive(Debug, Clone)]
+pub struct Upcoming<'a> {
+    schedule: &'a Schedule,
+    cursor: Option<SystemTime>,
+}
+
+impl Iterator for Upcoming<'_> {
+    type Item = SystemTime;
+
+    fn next(&mut self) -> Option<SystemTime> {
+        let next = self.schedule.next_after(self.cursor?);
+        self.cursor = next;
+        next
+    }
+}
+
+/// Starts building a fixed-interval [`Schedule`], e.g. `every().minutes(5)`.
+pub fn every() -> Every {
+    Every
+}
+
+/// Builder returned by [`every`].
+#[derive(Debug, Clone, Copy)]
+pub struct Every;
+
+impl Every {
+    /// Runs every `n` minutes, aligned to the hour (same as `*/n * * * *`).
+    ///
+    /// Steps restart at the top of each hour, so `minutes(25)` fires at :00,
+    /// :25 and :50. Panics unless `n` is in `1..=59`.
+    pub fn minutes(self, n: u32) -> Schedule {
+        assert!(
+            (1..=59).contains(&n),
+            "Every::minutes step must be in 1..=59, got {n}"
+        );
+        Schedule {
+            minutes: step_mask(0, 59, n),
+            ..Schedule::ANY
+        }
+    }
+
+    /// Runs every `n` hours on the hour (same as `0 */n * * *`).
+    ///
+    /// Steps restart at midnight, so `hours(5)` fires at 00:00, 05:00, ...,
+    /// 20:00 and then 00:00 again. Panics unless `n` is in `1..=23`.
+    pub fn hours(self, n: u32) -> Schedule {
+        assert!(
+            (1..=23).contains(&n),
+            "Every::hours step must be in 1..=23, got {n}"
+        );
+        Schedule {
+            minutes: 1,
+            hours: step_mask(0, 23, n),
+            ..Schedule::ANY
+        }
+    }
+
+    /// Runs at midnight every `n` days of the month (same as `0 0 */n * *`).
+    ///
+    /// Steps restart on the 1st of each month, so `days(10)` fires on the 1st,
+    /// 11th, 21st and 31st. Panics unless `n` is in `1..=31`.
+    pub fn days(self, n: u32) -> Schedule {
+        assert!(
+            (1..=31).contains(&n),
+            "Every::days step must be in 1..=31, got {n}"
+        );
+        Schedule {
+            minutes: 1,
+            hours: 1,
+            days: step_mask(1, 31, n),
+            days_restricted: true,
+            ..Schedule::ANY
+        }
+    }
+}
+
+fn parse_field(text: &str, field: &'static str, min: u32, max: u32) -> Result<u64, ScheduleError> {
+    let invalid = || ScheduleError::InvalidField {
+        field,
+        value: text.to_string(),
+    };
+    let number = |s: &str| {
+        s.parse::<u32>()
+            .ok()
+            .filter(|n| (min..=max).contains(n))
+            .ok_or_else(invalid)
+    };
+
+    let mut bits = 0;
+    for item in text.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (range, Some(step.parse::<u32>().map_err(|_| invalid())?)),
+            None => (item, None),
+        };
+        let (lo, hi) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((lo, hi)) => (number(lo)?, number(hi)?),
+                None if step.is_some() => (number(range)?, max),
+                None => (number(range)?, number(range)?),
+            },
+        };
+        if lo > hi || step == Some(0) {
+            return Err(invalid());
+        }
+        bits |= step_mask(lo, hi, step.unwrap_or(1));
+    }
+    Ok(bits)
+}
+
+const fn mask(lo: u32, hi: u32) -> u64 {
+    (u64::MAX >> (63 - hi)) & !((1 << lo) - 1)
+}
+
+fn step_mask(lo: u32, hi: u32, step: u32) -> u64 {
+    debug_assert!(step > 0, "step_mask needs a non-zero step");
+    (lo..=hi)
+        .step_by(step as usize)
+        .fold(0, |bits, n| bits | (1 << n))
+}
+
+fn has_bit(bits: u64, n: u32) -> bool {
+    bits & (1 << n) != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_matches_the_equivalent_cron_step() {
+        assert_eq!(every().minutes(25), "*/25 * * * *".parse().unwrap());
+        assert_eq!(every().hours(5), "0 */5 * * *".parse().unwrap());
+        assert_eq!(every().days(10), "0 0 */10 * *".parse().unwrap());
+    }
+
+    #[test]
+    #[should_panic(expected = "1..=59")]
+    fn every_rejects_zero_minutes() {
+        every().minutes(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "1..=59")]
+    fn every_rejects_minutes_past_the_hour() {
+        every().minutes(90);
+    }
+
+    #[test]
+    #[should_panic(expected = "1..=23")]
+    fn every_rejects_out_of_range_hours() {
+        every().hours(24);
+    }
+
+    #[test]
+    #[should_panic(expected = "1..=31")]
+    fn every_rejects_zero_days() {
+        every().days(0);
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt;
+
+/// Error returned when a string can't be split into shell words.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShellSplitError {
+    /// A `'` or `"` quote was never closed.
+    UnterminatedQuote(char),
+    /// The input ended with a lone backslash.
+    TrailingBackslash,
+}
+
+impl fmt::Display for ShellSplitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnterminatedQuote(quote) => write!(f, "unterminated {quote} quote"),
+            Self::TrailingBackslash => f.write_str("trailing backslash"),
+        }
+    }
+}
+
+impl std::error::Error for ShellSplitError {}
+
+/// POSIX-shell-style word splitting and quoting.
+pub trait ShellUtils {
+    /// Splits into words like a POSIX shell would, honoring `'single'` and
+    /// `"double"` quotes and backslash escapes. No expansion is performed.
+    fn split_shell_words(&self) -> Result<Vec<String>, ShellSplitError>;
+
+    /// Quotes the string so a POSIX shell reads it back as a single word.
+    fn shell_quote(&self) -> String;
+}
+
+impl ShellUtils for str {
+    fn split_shell_words(&self) -> Result<Vec<String>, ShellSplitError> {
+        let mut words = Vec::new();
+        let mut word = String::new();
+        let mut in_word = false;
+        let mut chars = self.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                c if c.is_whitespace() => {
+                    if in_word {
+                        words.push(std::mem::take(&mut word));
+                        in_word = false;
+                    }
+                }
+                '\\' => match chars.next() {
+                    Some('\n') => {}
+                    Some(c) => {
+                        word.push(c);
+                        in_word = true;
+                    }
+                    None => return Err(ShellSplitError::TrailingBackslash),
+                },
+                '\'' => {
+                    in_word = true;
+                    loop {
+                        match chars.next() {
+                            Some('\'') => break,
+                            Some(c) => word.push(c),
+                            None => return Err(ShellSplitError::UnterminatedQuote('\'')),
+                        }
+                    }
+                }
+                '"' => {
+                    in_word = true;
+                    loop {
+                        match chars.next() {
+                            Some('"') => break,
+                            Some('\\') => match chars.next() {
+                                Some('\n') => {}
+                                Some(c @ ('$' | '`' | '"' | '\\')) => word.push(c),
+                                Some(c) => {
+                                    word.push('\\');
+                                    word.push(c);
+                                }
+                                None => return Err(ShellSplitError::UnterminatedQuote('"')),
+                            },
+                            Some(c) => word.push(c),
+                            None => return Err(ShellSplitError::UnterminatedQuote('"')),
+                        }
+                    }
+                }
+                c => {
+                    word.push(c);
+                    in_word = true;
+                }
+            }
+        }
+        if in_word {
+            words.push(word);
+        }
+        Ok(words)
+    }
+
+    fn shell_quote(&self) -> String {
+        let is_safe = |c: char| c.is_ascii_alphanumeric() || "_-./=:,+@%".contains(c);
+        if !self.is_empty() && self.chars().all(is_safe) {
+            return self.to_string();
+        }
+        format!("'{}'", self.replace('\'', r"'\''"))
+    }
+}
@@ -0,0 +1,336 @@
+use std::{
+    fmt::{self, Write},
+    iter::Sum,
+    ops::{Add, AddAssign, Div, Mul, Sub, SubAssign},
+    str::FromStr,
+};
+
+const KIB: u64 = 1 << 10;
+const MIB: u64 = 1 << 20;
+const GIB: u64 = 1 << 30;
+const TIB: u64 = 1 << 40;
+const PIB: u64 = 1 << 50;
+const KB: u64 = 1_000;
+const MB: u64 = 1_000_000;
+const GB: u64 = 1_000_000_000;
+const TB: u64 = 1_000_000_000_000;
+const PB: u64 = 1_000_000_000_000_000;
+
+const BINARY_UNITS: [&str; 6] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+const SI_UNITS: [&str; 6] = ["B", "kB", "MB", "GB", "TB", "PB"];
+
+/// Error returned when a byte size string can't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseByteSizeError {
+    input: String,
+}
+
+impl fmt::Display for ParseByteSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid byte size `{}`", self.input)
+    }
+}
+
+impl std::error::Error for ParseByteSizeError {}
+
+/// A number of bytes with human-friendly parsing and formatting.
+///
+/// Parses `"512"`, `"512K"`, `"1.5 GiB"` or `"20MB"`. Units are case-insensitive;
+/// single letters and `KiB`-style units are binary (1024), while `KB`-style
+/// units are SI (1000).
+///
+/// Displays in binary units (`"1.5 GiB"`); use `{:#}` for SI units (`"1.6 GB"`).
+/// A precision like `{:.2}` sets the number of decimals; width and alignment
+/// flags like `{:>10}` pad the whole string.
+///
+/// The arithmetic operators follow `u64` overflow rules; use the `checked_*`
+/// and `saturating_*` methods when sizes come from input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ByteSize(u64);
+
+impl ByteSize {
+    /// `n` bytes.
+    pub const fn b(n: u64) -> Self {
+        Self(n)
+    }
+
+    /// `n` kibibytes (1024 bytes), saturating at `u64::MAX` bytes.
+    pub const fn kib(n: u64) -> Self {
+        Self(n.saturating_mul(KIB))
+    }
+
+    /// `n` mebibytes (1024² bytes), saturating at `u64::MAX` bytes.
+    pub const fn mib(n: u64) -> Self {
+        Self(n.saturating_mul(MIB))
+    }
+
+    /// `n` gibibytes (1024³ bytes), saturating at `u64::MAX` bytes.
+    pub const fn gib(n: u64) -> Self {
+        Self(n.saturating_mul(GIB))
+    }
+
+    /// `n` tebibytes (1024⁴ bytes), saturating at `u64::MAX` bytes.
+    pub const fn tib(n: u64) -> Self {
+        Self(n.saturating_mul(TIB))
+    }
+
+    /// `n` pebibytes (1024⁵ bytes), saturating at `u64::MAX` bytes.
+    pub const fn pib(n: u64) -> Self {
+        Self(n.saturating_mul(PIB))
+    }
+
+    /// `n` kilobytes (1000 bytes), saturating at `u64::MAX` bytes.
+    pub const fn kb(n: u64) -> Self {
+        Self(n.saturating_mul(KB))
+    }
+
+    /// `n` megabytes (1000² bytes), saturating at `u64::MAX` bytes.
+    pub const fn mb(n: u64) -> Self {
+        Self(n.saturating_mul(MB))
+    }
+
+    /// `n` gigabytes (1000³ bytes), saturating at `u64::MAX` bytes.
+    pub const fn gb(n: u64) -> Self {
+        Self(n.saturating_mul(GB))
+    }
+
+    /// `n` terabytes (1000⁴ bytes), saturating at `u64::MAX` bytes.
+    pub const fn tb(n: u64) -> Self {
+        Self(n.saturating_mul(TB))
+    }
+
+    /// `n` petabytes (1000⁵ bytes), saturating at `u64::MAX` bytes.
+    pub const fn pb(n: u64) -> Self {
+        Self(n.saturating_mul(PB))
+    }
+
+    /// Returns the number of bytes.
+    pub const fn as_u64(self) -> u64 {
+        self.0
+    }
+
+    /// Adds two sizes, or returns `None` on overflow.
+    pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_add(rhs.0) {
+            Some(n) => Some(Self(n)),
+            None => None,
+        }
+    }
+
+    /// Subtracts `rhs`, or returns `None` if it is larger than `self`.
+    pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+        match self.0.checked_sub(rhs.0) {
+            Some(n) => Some(Self(n)),
+            None => None,
+        }
+    }
+
+    /// Multiplies by `rhs`, or returns `None` on overflow.
+    pub const fn checked_mul(self, rhs: u64) -> Option<Self> {
+        match self.0.checked_mul(rhs) {
+            Some(n) => Some(Self(n)),
+            None => None,
+        }
+    }
+
+    /// Adds two sizes, stopping at `u64::MAX` bytes.
+    pub const fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+
+    /// Subtracts `rhs`, stopping at zero, e.g. for the space left in a quota.
+    pub const fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Multiplies by `rhs`, stopping at `u64::MAX` bytes.
+    pub const fn saturating_mul(self, rhs: u64) -> Self {
+        Self(self.0.saturating_mul(rhs))
+    }
+
+    /// Parses a size like `"1.5 GiB"`. Same as `str::parse`.
+    pub fn parse(s: &str) -> Result<Self, ParseByteSizeError> {
+        let invalid = || ParseByteSizeError {
+            input: s.to_string(),
+        };
+        let s = s.trim();
+        let split = s
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(s.len());
+        let (number, unit) = s.split_at(split);
+        let number: f64 = number.parse().map_err(|_| invalid())?;
+        let multiplier = match unit.trim_start().to_ascii_lowercase().as_str() {
+            "" | "b" => 1,
+            "k" | "ki" | "kib" => KIB,
+            "m" | "mi" | "mib" => MIB,
+            "g" | "gi" | "gib" => GIB,
+            "t" | "ti" | "tib" => TIB,
+            "p" | "pi" | "pib" => PIB,
+            "kb" => KB,
+            "mb" => MB,
+            "gb" => GB,
+            "tb" => TB,
+            "pb" => PB,
+            _ => return Err(invalid()),
+        };
+
+        let bytes = (number * multiplier as f64).round();
+        if !bytes.is_finite() || bytes >= u64::MAX as f64 {
+            return Err(invalid());
+        }
+        Ok(Self(bytes as u64))
+    }
+}
+
+impl fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (base, units) = if f.alternate() {
+            (1000.0, SI_UNITS)
+        } else {
+            (1024.0, BINARY_UNITS)
+        };
+        let precision = f.precision().unwrap_or(1);
+        let mut value = self.0 as f64;
+        let mut unit = 0;
+        while value >= base && unit < units.len() - 1 {
+            value /= base;
+            unit += 1;
+        }
+        // Rounding can carry into the next unit, e.g. 1023.96 KiB at one decimal.
+        let scale = 10f64.powi(precision.min(16) as i32);
+        if unit > 0 && unit < units.len() - 1 && (value * scale).round() / scale >= base {
+            value /= base;
+            unit += 1;
+        }
+        let text = if unit == 0 {
+            format!("{} B", self.0)
+        } else {
+            format!("{value:.precision$} {}", units[unit])
+        };
+
+        // `Formatter::pad` would treat the precision as a max length, so pad by hand.
+        let fill = f.width().unwrap_or(0).saturating_sub(text.chars().count());
+        let (left, right) = match f.align() {
+            Some(fmt::Alignment::Left) => (0, fill),
+            Some(fmt::Alignment::Center) => (fill / 2, fill - fill / 2),
+            Some(fmt::Alignment::Right) | None => (fill, 0),
+        };
+        for _ in 0..left {
+            f.write_char(f.fill())?;
+        }
+        f.write_str(&text)?;
+        for _ in 0..right {
+            f.write_char(f.fill())?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ByteSize {
+    type Err = ParseByteSizeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl From<u64> for ByteSize {
+    fn from(n: u64) -> Self {
+        Self(n)
+    }
+}
+
+impl From<ByteSize> for u64 {
+    fn from(size: ByteSize) -> Self {
+        size.0
+    }
+}
+
+impl Add for ByteSize {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Sub for ByteSize {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl AddAssign for ByteSize {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl SubAssign for ByteSize {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Mul<u64> for ByteSize {
+    type Output = Self;
+    fn mul(self, rhs: u64) -> Self {
+        Self(self.0 * rhs)
+    }
+}
+
+impl Div<u64> for ByteSize {
+    type Output = Self;
+    fn div(self, rhs: u64) -> Self {
+        Self(self.0 / rhs)
+    }
+}
+
+impl Sum for ByteSize {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self(0), Add::add)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checked_and_saturating_math_do_not_panic() {
+        let small = ByteSize::kib(1);
+        let big = ByteSize::mib(1);
+        assert_eq!(small.checked_sub(big), None);
+        assert_eq!(small.saturating_sub(big), ByteSize::b(0));
+        assert_eq!(ByteSize::b(u64::MAX).checked_add(small), None);
+        assert_eq!(
+            ByteSize::b(u64::MAX).saturating_mul(2),
+            ByteSize::b(u64::MAX)
+        );
+        assert_eq!(ByteSize::tib(u64::MAX), ByteSize::b(u64::MAX));
+    }
+
+    #[test]
+    fn display_honors_width_and_alignment() {
+        let size = ByteSize::mib(1536);
+        assert_eq!(format!("{size:>10}"), "   1.5 GiB");
+        assert_eq!(format!("{size:<10.2}|"), "1.50 GiB  |");
+        assert_eq!(format!("{size:*^11}"), "**1.5 GiB**");
+        assert_eq!(format!("{:#}", ByteSize::kb(2)), "2.0 kB");
+        assert_eq!(format!("{:5}", ByteSize::b(7)), "  7 B");
+    }
+
+    #[test]
+    fn display_steps_up_when_rounding_reaches_the_next_unit() {
+        assert_eq!(ByteSize::b(MIB - 1).to_string(), "1.0 MiB");
+        assert_eq!(format!("{:#}", ByteSize::b(MB - 1)), "1.0 MB");
+        assert_eq!(format!("{:.3}", ByteSize::b(MIB - 1)), "1023.999 KiB");
+        assert_eq!(ByteSize::b(KIB - 1).to_string(), "1023 B");
+    }
+
+    #[test]
+    fn peta_constructors_match_parsing() {
+        assert_eq!(ByteSize::pib(2), "2 PiB".parse().unwrap());
+        assert_eq!(ByteSize::pb(2), "2PB".parse().unwrap());
+    }
+}
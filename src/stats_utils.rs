@@ -0,0 +1,219 @@
+use crate::rand_utils::Rng;
+
+/// Running statistics over a stream of samples.
+///
+/// Count, mean, standard deviation, min and max are updated incrementally in
+/// constant memory. Percentiles need the samples themselves: [`new`](Self::new)
+/// keeps every one, so memory grows with the stream, while
+/// [`with_reservoir`](Self::with_reservoir) keeps a bounded random subset.
+#[derive(Debug, Clone, Default)]
+pub struct StatsAccumulator {
+    samples: Vec<f64>,
+    reservoir: Option<Reservoir>,
+    count: usize,
+    mean: f64,
+    m2: f64,
+    min: Option<f64>,
+    max: Option<f64>,
+}
+
+/// Capacity and generator for reservoir sampling (Algorithm R).
+#[derive(Debug, Clone)]
+struct Reservoir {
+    capacity: usize,
+    rng: Rng,
+}
+
+impl StatsAccumulator {
+    /// Creates an empty accumulator that keeps every sample for exact percentiles.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty accumulator that keeps at most `capacity` samples.
+    ///
+    /// Once full, each new sample replaces a random kept one so the kept set
+    /// stays a uniform sample of the stream. Percentiles are then estimates;
+    /// the other statistics stay exact. Panics if `capacity` is zero.
+    pub fn with_reservoir(capacity: usize) -> Self {
+        assert!(capacity > 0, "reservoir capacity must be non-zero");
+        Self {
+            samples: Vec::with_capacity(capacity),
+            reservoir: Some(Reservoir {
+                capacity,
+                rng: Rng::new(),
+            }),
+            ..Self::default()
+        }
+    }
+
+    /// Adds a sample.
+    pub fn push(&mut self, x: f64) {
+        self.count += 1;
+        match &mut self.reservoir {
+            Some(r) if self.samples.len() >= r.capacity => {
+                let slot = r.rng.range(0, self.count as i64 - 1) as usize;
+                if let Some(kept) = self.samples.get_mut(slot) {
+                    *kept = x;
+                }
+            }
+            _ => self.samples.push(x),
+        }
+        let n = self.count as f64;
+        let delta = x - self.mean;
+        self.mean += delta / n;
+        self.m2 += delta * (x - self.mean);
+        self.min = Some(self.min.map_or(x, |min| min.min(x)));
+        self.max = Some(self.max.map_or(x, |max| max.max(x)));
+    }
+
+    /// Returns the number of samples pushed so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns `true` if no samples were pushed.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Returns the arithmetic mean, or `None` if empty.
+    pub fn mean(&self) -> Option<f64> {
+        (!self.is_empty()).then_some(self.mean)
+    }
+
+    /// Returns the population standard deviation, or `None` if empty.
+    pub fn stddev(&self) -> Option<f64> {
+        (!self.is_empty()).then(|| (self.m2 / self.count as f64).sqrt())
+    }
+
+    /// Returns the smallest sample, or `None` if empty.
+    pub fn min(&self) -> Option<f64> {
+        self.min
+    }
+
+    /// Returns the largest sample, or `None` if empty.
+    pub fn max(&self) -> Option<f64> {
+        self.max
+    }
+
+    /// Returns the 95th percentile, or `None` if empty.
+    pub fn p95(&self) -> Option<f64> {
+        self.percentile(95.0)
+    }
+
+    /// Returns the `p`-th percentile (nearest-rank), or `None` if empty.
+    ///
+    /// `p` is clamped to `0.0..=100.0`. Each call sorts a copy of the kept
+    /// samples, so it costs O(n log n) in their number.
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+        let mut sorted = self.samples.clone();
+        sorted.sort_by(f64::total_cmp);
+        let rank = (p.clamp(0.0, 100.0) / 100.0 * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.saturating_sub(1)])
+    }
+
+    /// Removes all samples, keeping the reservoir capacity if one was set.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.count = 0;
+        self.mean = 0.0;
+        self.m2 = 0.0;
+        self.min = None;
+        self.max = None;
+    }
+}
+
+impl Extend<f64> for StatsAccumulator {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter {
+            self.push(x);
+        }
+    }
+}
+
+impl FromIterator<f64> for StatsAccumulator {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut stats = Self::new();
+        stats.extend(iter);
+        stats
+    }
+}
+
+/// Exponentially weighted moving average.
+#[derive(Debug, Clone, Copy)]
+pub struct Ewma {
+    alpha: f64,
+    value: Option<f64>,
+}
+
+impl Ewma {
+    /// Creates an average where each new sample has weight `alpha`.
+    ///
+    /// Panics if `alpha` is not in `(0.0, 1.0]`.
+    pub fn new(alpha: f64) -> Self {
+        assert!(
+            alpha > 0.0 && alpha <= 1.0,
+            "Ewma alpha must be in (0.0, 1.0], got {alpha}"
+        );
+        Self { alpha, value: None }
+    }
+
+    /// Adds a sample and returns the updated average.
+    pub fn push(&mut self, x: f64) -> f64 {
+        let next = match self.value {
+            Some(prev) => prev + self.alpha * (x - prev),
+            None => x,
+        };
+        self.value = Some(next);
+        next
+    }
+
+    /// Returns the current average, or `None` before the first sample.
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+
+    /// Forgets all samples.
+    pub fn reset(&mut self) {
+        self.value = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reservoir_bounds_memory_but_keeps_exact_moments() {
+        let mut stats = StatsAccumulator::with_reservoir(100);
+        stats.extend((1..=10_000).map(f64::from));
+        assert_eq!(stats.samples.len(), 100);
+        assert_eq!(stats.count(), 10_000);
+        assert_eq!(stats.mean(), Some(5_000.5));
+        assert_eq!((stats.min(), stats.max()), (Some(1.0), Some(10_000.0)));
+        let p95 = stats.p95().unwrap();
+        assert!((1.0..=10_000.0).contains(&p95));
+    }
+
+    #[test]
+    fn clear_keeps_the_reservoir_capacity() {
+        let mut stats = StatsAccumulator::with_reservoir(3);
+        stats.extend([1.0, 2.0, 3.0, 4.0]);
+        stats.clear();
+        assert!(stats.is_empty());
+        stats.extend([5.0, 6.0, 7.0, 8.0]);
+        assert_eq!(stats.samples.len(), 3);
+        assert_eq!(stats.count(), 4);
+    }
+
+    #[test]
+    fn unbounded_percentiles_are_exact() {
+        let stats: StatsAccumulator = (1..=100).map(f64::from).collect();
+        assert_eq!(stats.p95(), Some(95.0));
+        assert_eq!(stats.percentile(0.0), Some(1.0));
+    }
+}
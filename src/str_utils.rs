@@ -1,3 +1,5 @@
+use crate::term_utils::width::{WidthUtils, is_combining_mark, split_at_width};
+
 /// Extra methods for string slices (`&str`).
 pub trait StrUtils {
     /// Returns `true` if all strings in the iterator exist in the main string.
@@ -12,6 +14,40 @@ pub trait StrUtils {
 
     /// Returns a new string with the first letter capitalized.
     fn to_title_case(&self) -> String;
+
+    /// Word-wraps the string into lines of at most `width` display columns.
+    ///
+    /// Existing line breaks are kept and words longer than `width` are split.
+    /// ANSI escape sequences take no space and are never split.
+    fn wrap(&self, width: usize) -> Vec<String>;
+
+    /// Returns each line with surrounding whitespace trimmed.
+    fn lines_trimmed(&self) -> impl Iterator<Item = &str>;
+
+    /// Returns the lines that aren't empty or whitespace-only.
+    fn non_empty_lines(&self) -> impl Iterator<Item = &str>;
+
+    /// Applies `f` to every line and joins the results with `\n`.
+    fn map_lines<F: FnMut(&str) -> String>(&self, f: F) -> String;
+
+    /// Returns a new string with `prefix` prepended to every line.
+    fn prefix_lines(&self, prefix: &str) -> String;
+
+    /// Returns the `n`-th line (zero-based), if any.
+    fn line_at(&self, n: usize) -> Option<&str>;
+
+    /// Trims the string and collapses every run of whitespace into one space.
+    fn normalize_whitespace(&self) -> String;
+
+    /// Folds accented Latin letters to ASCII (`"Crème Brûlée"` → `"Creme Brulee"`).
+    ///
+    /// Best effort: combining marks are dropped and characters without a
+    /// known folding are kept as-is.
+    fn remove_diacritics(&self) -> String;
+
+    /// Builds a key for dedup and lookups: diacritics removed, whitespace
+    /// normalized and lowercased.
+    fn to_comparable(&self) -> String;
 }
 
 impl StrUtils for str {
@@ -34,4 +70,168 @@ impl StrUtils for str {
             Some(f) => f.to_uppercase().collect::<String>() + chars.as_str(),
         }
     }
+
+    fn wrap(&self, width: usize) -> Vec<String> {
+        let width = width.max(1);
+        let mut lines = Vec::new();
+        for paragraph in self.lines() {
+            let mut line = String::new();
+            let mut line_len = 0;
+            for word in paragraph.split_whitespace() {
+                let mut word = word;
+                let mut word_len = word.display_width();
+                if line_len > 0 && line_len + 1 + word_len > width {
+                    lines.push(std::mem::take(&mut line));
+                    line_len = 0;
+                }
+                while word_len > width {
+                    let split = split_at_width(word, width);
+                    if line_len > 0 {
+                        lines.push(std::mem::take(&mut line));
+                        line_len = 0;
+                    }
+                    lines.push(word[..split].to_string());
+                    word = &word[split..];
+                    word_len = word.display_width();
+                }
+                if word.is_empty() {
+                    continue;
+                }
+                if line_len > 0 {
+                    line.push(' ');
+                    line_len += 1;
+                }
+                line.push_str(word);
+                line_len += word_len;
+            }
+            lines.push(line);
+        }
+        if lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines
+    }
+
+    fn lines_trimmed(&self) -> impl Iterator<Item = &str> {
+        self.lines().map(str::trim)
+    }
+
+    fn non_empty_lines(&self) -> impl Iterator<Item = &str> {
+        self.lines().filter(|line| !line.trim().is_empty())
+    }
+
+    fn map_lines<F: FnMut(&str) -> String>(&self, f: F) -> String {
+        self.lines().map(f).collect::<Vec<_>>().join("\n")
+    }
+
+    fn prefix_lines(&self, prefix: &str) -> String {
+        self.map_lines(|line| format!("{prefix}{line}"))
+    }
+
+    fn line_at(&self, n: usize) -> Option<&str> {
+        self.lines().nth(n)
+    }
+
+    fn normalize_whitespace(&self) -> String {
+        self.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    fn remove_diacritics(&self) -> String {
+        let mut out = String::with_capacity(self.len());
+        for c in self.chars() {
+            if is_combining_mark(c) {
+                continue;
+            }
+            match fold_latin(c) {
+                Some(folded) => out.push_str(folded),
+                None => out.push(c),
+            }
+        }
+        out
+    }
+
+    fn to_comparable(&self) -> String {
+        self.remove_diacritics()
+            .normalize_whitespace()
+            .to_lowercase()
+    }
+}
+
+/// ASCII folding for the Latin-1 Supplement and Latin Extended-A letters.
+fn fold_latin(c: char) -> Option<&'static str> {
+    Some(match c {
+        'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' | 'Ā' | 'Ă' | 'Ą' => "A",
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'Æ' => "AE",
+        'æ' => "ae",
+        'Ç' | 'Ć' | 'Ĉ' | 'Ċ' | 'Č' => "C",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'Ð' | 'Ď' | 'Đ' => "D",
+        'ð' | 'ď' | 'đ' => "d",
+        'È' | 'É' | 'Ê' | 'Ë' | 'Ē' | 'Ĕ' | 'Ė' | 'Ę' | 'Ě' => "E",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'Ĝ' | 'Ğ' | 'Ġ' | 'Ģ' => "G",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'Ĥ' | 'Ħ' => "H",
+        'ĥ' | 'ħ' => "h",
+        'Ì' | 'Í' | 'Î' | 'Ï' | 'Ĩ' | 'Ī' | 'Ĭ' | 'Į' | 'İ' => "I",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'Ĳ' => "IJ",
+        'ĳ' => "ij",
+        'Ĵ' => "J",
+        'ĵ' => "j",
+        'Ķ' => "K",
+        'ķ' | 'ĸ' => "k",
+        'Ĺ' | 'Ļ' | 'Ľ' | 'Ŀ' | 'Ł' => "L",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'Ñ' | 'Ń' | 'Ņ' | 'Ň' | 'Ŋ' => "N",
+        'ñ' | 'ń' | 'ņ' | 'ň' | 'ŉ' | 'ŋ' => "n",
+        'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' | 'Ō' | 'Ŏ' | 'Ő' => "O",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'Œ' => "OE",
+        'œ' => "oe",
+        'Ŕ' | 'Ŗ' | 'Ř' => "R",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'Ś' | 'Ŝ' | 'Ş' | 'Š' => "S",
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ſ' => "s",
+        'ß' => "ss",
+        'Ţ' | 'Ť' | 'Ŧ' => "T",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'Þ' => "TH",
+        'þ' => "th",
+        'Ù' | 'Ú' | 'Û' | 'Ü' | 'Ũ' | 'Ū' | 'Ŭ' | 'Ů' | 'Ű' | 'Ų' => "U",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'Ŵ' => "W",
+        'ŵ' => "w",
+        'Ý' | 'Ŷ' | 'Ÿ' => "Y",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'Ź' | 'Ż' | 'Ž' => "Z",
+        'ź' | 'ż' | 'ž' => "z",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_measures_visible_width_and_keeps_escapes_whole() {
+        let lines = "a\x1b[31mred\x1b[0m word here".wrap(5);
+        assert_eq!(lines, ["a\x1b[31mred\x1b[0m", "word", "here"]);
+        for line in &lines {
+            assert!(line.display_width() <= 5);
+        }
+    }
+
+    #[test]
+    fn wrap_splits_long_colored_words_on_visible_columns() {
+        let lines = "\x1b[1mabcdef\x1b[0m".wrap(4);
+        assert_eq!(lines, ["\x1b[1mabcd", "ef\x1b[0m"]);
+    }
+
+    #[test]
+    fn wrap_counts_wide_characters_as_two_columns() {
+        assert_eq!("日本語 ok".wrap(4), ["日本", "語", "ok"]);
+    }
 }
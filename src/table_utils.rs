@@ -0,0 +1,207 @@
+use crate::{str_utils::StrUtils, term_utils::width::WidthUtils};
+
+/// Horizontal alignment of a table column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+    Center,
+}
+
+/// Plain-text table builder for CLI reports, e.g. `Table::new(["Name", "Size"])`.
+#[derive(Debug, Clone, Default)]
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+    aligns: Vec<Align>,
+    max_widths: Vec<Option<usize>>,
+}
+
+impl Table {
+    /// Creates a table with the given column headers.
+    pub fn new<I, S>(headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            headers: headers.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Appends a row. Missing cells render empty; extra cells add columns.
+    pub fn add_row<I, S>(&mut self, row: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.rows.push(row.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets the alignment of column `col`.
+    pub fn align(&mut self, col: usize, align: Align) -> &mut Self {
+        if self.aligns.len() <= col {
+            self.aligns.resize(col + 1, Align::Left);
+        }
+        self.aligns[col] = align;
+        self
+    }
+
+    /// Limits column `col` to `width` characters, wrapping longer cells in `render()`.
+    pub fn max_width(&mut self, col: usize, width: usize) -> &mut Self {
+        if self.max_widths.len() <= col {
+            self.max_widths.resize(col + 1, None);
+        }
+        self.max_widths[col] = Some(width.max(1));
+        self
+    }
+
+    /// Returns the number of data rows.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns `true` if no rows were added.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Renders the table with ASCII borders.
+    pub fn render(&self) -> String {
+        let columns = self.columns();
+        let wrap_row = |row: &[String]| -> Vec<Vec<String>> {
+            (0..columns)
+                .map(|col| {
+                    let cell = row.get(col).map_or("", String::as_str);
+                    match self.max_width_of(col) {
+                        Some(width) => cell.wrap(width),
+                        None => cell.lines().map(str::to_string).collect(),
+                    }
+                })
+                .collect()
+        };
+        let header = wrap_row(&self.headers);
+        let rows: Vec<_> = self.rows.iter().map(|row| wrap_row(row)).collect();
+
+        let mut widths = vec![0; columns];
+        for row in std::iter::once(&header).chain(&rows) {
+            for (col, lines) in row.iter().enumerate() {
+                for line in lines {
+                    widths[col] = widths[col].max(line.display_width());
+                }
+            }
+        }
+
+        let border = widths.iter().fold(String::from("+"), |mut line, &width| {
+            line.push_str(&"-".repeat(width + 2));
+            line.push('+');
+            line
+        });
+
+        let mut out = String::new();
+        out.push_str(&border);
+        out.push('\n');
+        self.render_wrapped_row(&mut out, &header, &widths);
+        out.push_str(&border);
+        out.push('\n');
+        for row in &rows {
+            self.render_wrapped_row(&mut out, row, &widths);
+        }
+        if !rows.is_empty() {
+            out.push_str(&border);
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Renders the table as GitHub-flavored Markdown. Cells are not wrapped.
+    pub fn render_markdown(&self) -> String {
+        let columns = self.columns();
+        let escape = |cell: &str| cell.replace('|', "\\|").replace('\n', "<br>");
+        let cells = |row: &[String]| -> Vec<String> {
+            (0..columns)
+                .map(|col| escape(row.get(col).map_or("", String::as_str)))
+                .collect()
+        };
+        let header = cells(&self.headers);
+        let rows: Vec<_> = self.rows.iter().map(|row| cells(row)).collect();
+
+        let mut widths = vec![3; columns];
+        for row in std::iter::once(&header).chain(&rows) {
+            for (col, cell) in row.iter().enumerate() {
+                widths[col] = widths[col].max(cell.display_width());
+            }
+        }
+
+        let mut out = String::new();
+        let mut push_line = |row: &[String]| {
+            out.push('|');
+            for (col, cell) in row.iter().enumerate() {
+                out.push(' ');
+                out.push_str(&pad(cell, widths[col], self.align_of(col)));
+                out.push_str(" |");
+            }
+            out.push('\n');
+        };
+        push_line(&header);
+        let separator: Vec<String> = (0..columns)
+            .map(|col| {
+                let dashes = widths[col];
+                match self.align_of(col) {
+                    Align::Left => "-".repeat(dashes),
+                    Align::Right => format!("{}:", "-".repeat(dashes - 1)),
+                    Align::Center => format!(":{}:", "-".repeat(dashes - 2)),
+                }
+            })
+            .collect();
+        push_line(&separator);
+        for row in &rows {
+            push_line(row);
+        }
+        out
+    }
+
+    fn columns(&self) -> usize {
+        self.rows
+            .iter()
+            .map(Vec::len)
+            .chain([self.headers.len()])
+            .max()
+            .unwrap_or(0)
+    }
+
+    fn align_of(&self, col: usize) -> Align {
+        self.aligns.get(col).copied().unwrap_or_default()
+    }
+
+    fn max_width_of(&self, col: usize) -> Option<usize> {
+        self.max_widths.get(col).copied().flatten()
+    }
+
+    fn render_wrapped_row(&self, out: &mut String, row: &[Vec<String>], widths: &[usize]) {
+        let height = row.iter().map(Vec::len).max().unwrap_or(0).max(1);
+        for i in 0..height {
+            out.push('|');
+            for (col, lines) in row.iter().enumerate() {
+                let line = lines.get(i).map_or("", String::as_str);
+                out.push(' ');
+                out.push_str(&pad(line, widths[col], self.align_of(col)));
+                out.push_str(" |");
+            }
+            out.push('\n');
+        }
+    }
+}
+
+fn pad(text: &str, width: usize, align: Align) -> String {
+    let fill = width.saturating_sub(text.display_width());
+    let (left, right) = match align {
+        Align::Left => (0, fill),
+        Align::Right => (fill, 0),
+        Align::Center => (fill / 2, fill - fill / 2),
+    };
+    format!("{}{}{}", " ".repeat(left), text, " ".repeat(right))
+}
@@ -0,0 +1,165 @@
+/// Width helpers that ignore ANSI escapes and count wide characters as two columns.
+pub trait WidthUtils {
+    /// Returns the number of terminal columns the string occupies.
+    ///
+    /// ANSI escape sequences take no space, East Asian wide characters and most
+    /// emoji take two, and combining marks take none. This is an approximation
+    /// of what terminals do, not a full Unicode width implementation.
+    fn display_width(&self) -> usize;
+
+    /// Pads with spaces on the right up to `width` display columns.
+    fn pad_display(&self, width: usize) -> String;
+
+    /// Returns the string with ANSI escape sequences removed.
+    fn strip_ansi(&self) -> String;
+}
+
+impl WidthUtils for str {
+    fn display_width(&self) -> usize {
+        visible_chars(self).map(char_width).sum()
+    }
+
+    fn pad_display(&self, width: usize) -> String {
+        let fill = width.saturating_sub(self.display_width());
+        format!("{}{}", self, " ".repeat(fill))
+    }
+
+    fn strip_ansi(&self) -> String {
+        visible_chars(self).collect()
+    }
+}
+
+/// Aligns rows of cells into columns separated by two spaces.
+///
+/// Column widths use [`WidthUtils::display_width`], so colored cells line up.
+/// The last cell of each row isn't padded.
+pub fn align_columns<R, S>(rows: &[R]) -> Vec<String>
+where
+    R: AsRef<[S]>,
+    S: AsRef<str>,
+{
+    let mut widths = Vec::new();
+    for row in rows {
+        for (col, cell) in row.as_ref().iter().enumerate() {
+            let width = cell.as_ref().display_width();
+            match widths.get_mut(col) {
+                Some(max) => *max = width.max(*max),
+                None => widths.push(width),
+            }
+        }
+    }
+
+    rows.iter()
+        .map(|row| {
+            let cells = row.as_ref();
+            let mut line = String::new();
+            for (col, cell) in cells.iter().enumerate() {
+                if col + 1 == cells.len() {
+                    line.push_str(cell.as_ref());
+                } else {
+                    line.push_str(&cell.as_ref().pad_display(widths[col]));
+                    line.push_str("  ");
+                }
+            }
+            line
+        })
+        .collect()
+}
+
+/// Iterates over the characters of `s` outside ANSI escape sequences.
+fn visible_chars(s: &str) -> impl Iterator<Item = char> + '_ {
+    visible_char_indices(s).map(|(_, c)| c)
+}
+
+/// Like [`visible_chars`], but also yields each character's byte offset.
+fn visible_char_indices(s: &str) -> impl Iterator<Item = (usize, char)> + '_ {
+    let mut chars = s.char_indices();
+    std::iter::from_fn(move || {
+        loop {
+            let (i, c) = chars.next()?;
+            if c != '\x1b' {
+                return Some((i, c));
+            }
+            match chars.next().map(|(_, c)| c) {
+                // CSI: ESC [ params... final byte in @..=~
+                Some('[') => {
+                    for (_, c) in chars.by_ref() {
+                        if ('@'..='~').contains(&c) {
+                            break;
+                        }
+                    }
+                }
+                // OSC: ESC ] ... terminated by BEL or ESC \
+                Some(']') => {
+                    while let Some((_, c)) = chars.next() {
+                        if c == '\x07' {
+                            break;
+                        }
+                        if c == '\x1b' {
+                            chars.next();
+                            break;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    })
+}
+
+/// Returns the byte offset where `s` first exceeds `width` display columns.
+///
+/// At least one visible character is always kept before the split so callers
+/// make progress, and escape sequences stay with the text before them.
+pub(crate) fn split_at_width(s: &str, width: usize) -> usize {
+    let mut used = 0;
+    for (i, c) in visible_char_indices(s) {
+        let w = char_width(c);
+        if used > 0 && used + w > width {
+            return i;
+        }
+        used += w;
+    }
+    s.len()
+}
+
+/// Returns `true` for combining marks (accents and other diacritics applied
+/// to the previous character).
+pub(crate) fn is_combining_mark(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x0300..=0x036F
+            | 0x0483..=0x0489
+            | 0x0591..=0x05BD
+            | 0x0610..=0x061A
+            | 0x064B..=0x065F
+            | 0x1AB0..=0x1AFF
+            | 0x1DC0..=0x1DFF
+            | 0x20D0..=0x20FF
+            | 0xFE20..=0xFE2F
+    )
+}
+
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x00..=0x1F | 0x7F..=0x9F => 0,
+        0x200B..=0x200F | 0xFE00..=0xFE0F => 0,
+        _ if is_combining_mark(c) => 0,
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x2FFFD
+        | 0x30000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
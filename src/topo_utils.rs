@@ -0,0 +1,168 @@
+use std::{collections::HashMap, fmt, hash::Hash};
+
+/// Error returned when the dependency graph contains a cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<T> {
+    cycle: Vec<T>,
+}
+
+impl<T> CycleError<T> {
+    /// Returns the items forming the cycle, each depending on the next.
+    ///
+    /// The first item is repeated at the end.
+    pub fn cycle(&self) -> &[T] {
+        &self.cycle
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for CycleError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("dependency cycle: ")?;
+        for (i, item) in self.cycle.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" -> ")?;
+            }
+            write!(f, "{item}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: fmt::Display + fmt::Debug> std::error::Error for CycleError<T> {}
+
+/// Orders items so every item comes after its dependencies.
+///
+/// Items are fed as `(item, deps)` pairs. Dependencies that are never added
+/// themselves are treated as items without dependencies. Ties keep insertion order.
+#[derive(Debug, Clone)]
+pub struct Resolver<T> {
+    items: Vec<T>,
+    index: HashMap<T, usize>,
+    deps: Vec<Vec<usize>>,
+}
+
+impl<T: Eq + Hash + Clone> Resolver<T> {
+    /// Creates an empty resolver.
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            index: HashMap::new(),
+            deps: Vec::new(),
+        }
+    }
+
+    /// Adds `item` depending on `deps`. Adding the same item again merges its deps.
+    pub fn add<I: IntoIterator<Item = T>>(&mut self, item: T, deps: I) -> &mut Self {
+        let id = self.id_of(item);
+        for dep in deps {
+            let dep = self.id_of(dep);
+            if !self.deps[id].contains(&dep) {
+                self.deps[id].push(dep);
+            }
+        }
+        self
+    }
+
+    /// Returns every item in dependency order.
+    pub fn resolve(&self) -> Result<Vec<T>, CycleError<T>> {
+        Ok(self.parallel_batches()?.into_iter().flatten().collect())
+    }
+
+    /// Groups items into batches that can run in parallel.
+    ///
+    /// Every item in a batch only depends on items from earlier batches.
+    pub fn parallel_batches(&self) -> Result<Vec<Vec<T>>, CycleError<T>> {
+        let mut pending: Vec<usize> = self.deps.iter().map(Vec::len).collect();
+        let mut dependents = vec![Vec::new(); self.items.len()];
+        for (id, deps) in self.deps.iter().enumerate() {
+            for &dep in deps {
+                dependents[dep].push(id);
+            }
+        }
+
+        let mut batches = Vec::new();
+        let mut ready: Vec<usize> = (0..self.items.len())
+            .filter(|&id| pending[id] == 0)
+            .collect();
+        let mut done = 0;
+        while !ready.is_empty() {
+            done += ready.len();
+            let mut next = Vec::new();
+            for &id in &ready {
+                for &dependent in &dependents[id] {
+                    pending[dependent] -= 1;
+                    if pending[dependent] == 0 {
+                        next.push(dependent);
+                    }
+                }
+            }
+            next.sort_unstable();
+            batches.push(ready.iter().map(|&id| self.items[id].clone()).collect());
+            ready = next;
+        }
+
+        if done < self.items.len() {
+            return Err(self.find_cycle(&pending));
+        }
+        Ok(batches)
+    }
+
+    fn id_of(&mut self, item: T) -> usize {
+        if let Some(&id) = self.index.get(&item) {
+            return id;
+        }
+        let id = self.items.len();
+        self.index.insert(item.clone(), id);
+        self.items.push(item);
+        self.deps.push(Vec::new());
+        id
+    }
+
+    /// Walks unresolved deps until an item repeats. Every unresolved item
+    /// still has an unresolved dep, so the walk always finds a cycle.
+    fn find_cycle(&self, pending: &[usize]) -> CycleError<T> {
+        let unresolved = |id: usize| pending[id] > 0;
+        let mut path = Vec::new();
+        let mut seen_at = HashMap::new();
+        let mut id = (0..self.items.len())
+            .find(|&id| unresolved(id))
+            .expect("an unresolved item");
+        while !seen_at.contains_key(&id) {
+            seen_at.insert(id, path.len());
+            path.push(id);
+            id = *self.deps[id]
+                .iter()
+                .find(|&&dep| unresolved(dep))
+                .expect("an unresolved dependency");
+        }
+
+        let mut cycle: Vec<T> = path[seen_at[&id]..]
+            .iter()
+            .map(|&id| self.items[id].clone())
+            .collect();
+        cycle.push(self.items[id].clone());
+        CycleError { cycle }
+    }
+}
+
+impl<T: Eq + Hash + Clone> Default for Resolver<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Eq + Hash + Clone, I: IntoIterator<Item = T>> Extend<(T, I)> for Resolver<T> {
+    fn extend<J: IntoIterator<Item = (T, I)>>(&mut self, pairs: J) {
+        for (item, deps) in pairs {
+            self.add(item, deps);
+        }
+    }
+}
+
+impl<T: Eq + Hash + Clone, I: IntoIterator<Item = T>> FromIterator<(T, I)> for Resolver<T> {
+    fn from_iter<J: IntoIterator<Item = (T, I)>>(pairs: J) -> Self {
+        let mut resolver = Self::new();
+        resolver.extend(pairs);
+        resolver
+    }
+}
@@ -0,0 +1,141 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        mpsc::{self, RecvTimeoutError, Sender},
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, SystemTime},
+};
+
+use crate::cancel_utils::{CancelRegistration, CancellationToken};
+
+/// Stops the watcher when dropped.
+///
+/// Dropping waits for the watcher thread to finish, including a callback
+/// that is currently running.
+#[derive(Debug)]
+pub struct WatchGuard {
+    stop: Option<Arc<Sender<()>>>,
+    handle: Option<JoinHandle<()>>,
+    cancel: Option<CancelRegistration>,
+}
+
+impl WatchGuard {
+    /// Stops the watcher. Same as dropping the guard.
+    pub fn stop(self) {}
+}
+
+impl Drop for WatchGuard {
+    fn drop(&mut self) {
+        // Closing the channel wakes the thread immediately.
+        self.stop.take();
+        if let Some(handle) = self.handle.take()
+            && handle.thread().id() != thread::current().id()
+        {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Polls `path` every `interval` on a background thread and calls `f` when it changes.
+///
+/// A change is a different modification time or size, or the file appearing
+/// or disappearing. Changes are debounced: `f` runs once the file has stayed
+/// unchanged for a full interval, so a burst of writes triggers one call.
+pub fn watch<P, F>(path: P, interval: Duration, mut f: F) -> WatchGuard
+where
+    P: Into<PathBuf>,
+    F: FnMut(&Path) + Send + 'static,
+{
+    let path = path.into();
+    let (stop, stopped) = mpsc::channel::<()>();
+    let handle = thread::spawn(move || {
+        let mut last = fingerprint(&path);
+        let mut pending = false;
+        while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+            let current = fingerprint(&path);
+            if current != last {
+                last = current;
+                pending = true;
+            } else if pending {
+                pending = false;
+                f(&path);
+            }
+        }
+    });
+
+    WatchGuard {
+        stop: Some(Arc::new(stop)),
+        handle: Some(handle),
+        cancel: None,
+    }
+}
+
+/// Same as [`watch`], but also stops once `token` is cancelled.
+pub fn watch_until<P, F>(path: P, interval: Duration, token: &CancellationToken, f: F) -> WatchGuard
+where
+    P: Into<PathBuf>,
+    F: FnMut(&Path) + Send + 'static,
+{
+    let mut guard = watch(path, interval, f);
+    // Only the guard owns the sender, so dropping it still closes the channel.
+    // The registration is dropped with the guard, removing the callback.
+    if let Some(stop) = guard.stop.as_ref().map(Arc::downgrade) {
+        guard.cancel = Some(token.register(move || {
+            if let Some(stop) = stop.upgrade() {
+                let _ = stop.send(());
+            }
+        }));
+    }
+    guard
+}
+
+fn fingerprint(path: &Path) -> Option<(Option<SystemTime>, u64)> {
+    let meta = fs::metadata(path).ok()?;
+    Some((meta.modified().ok(), meta.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dropping_watch_until_guard_before_cancel_does_not_hang() {
+        let token = CancellationToken::new();
+        let (done, finished) = mpsc::channel();
+        let dropper = token.clone();
+        thread::spawn(move || {
+            drop(watch_until(
+                "/nonexistent/utilz-watch",
+                Duration::from_millis(10),
+                &dropper,
+                |_| {},
+            ));
+            let _ = done.send(());
+        });
+        assert!(finished.recv_timeout(Duration::from_secs(5)).is_ok());
+        token.cancel();
+    }
+
+    #[test]
+    fn cancelling_token_stops_watch_until() {
+        let token = CancellationToken::new();
+        let guard = watch_until(
+            "/nonexistent/utilz-watch",
+            Duration::from_secs(60),
+            &token,
+            |_| {},
+        );
+        token.cancel();
+        let handle = guard.handle.as_ref().unwrap();
+        for _ in 0..500 {
+            if handle.is_finished() {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        panic!("watcher thread still running after cancel");
+    }
+}